@@ -0,0 +1,13 @@
+use scraping_sample::wixoss::schema::{card_json_schema_string, card_schema_file_name};
+use std::path::Path;
+
+// cargo run --example export_card_schema -- [output_dir]
+// スキーマを変更したら CARD_SCHEMA_VERSION を上げてから再生成すること
+fn main() {
+    let dir: String = std::env::args().nth(1).unwrap_or("./schema".into());
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let path = Path::new(&dir).join(card_schema_file_name());
+    std::fs::write(&path, card_json_schema_string()).unwrap();
+    println!("{}", path.display());
+}
@@ -0,0 +1,16 @@
+use scraping_sample::try_mkdir;
+use scraping_sample::wixoss::feature_index::{feature_index_json, feature_index_typescript};
+use std::fs;
+use std::path::Path;
+
+// cargo run --example export_feature_index -- [output_dir]
+fn main() {
+    let dir: String = std::env::args().nth(1).unwrap_or("./feature_index".into());
+    let dir: &Path = Path::new(&dir);
+    try_mkdir(dir).unwrap();
+
+    fs::write(dir.join("card_features.json"), feature_index_json()).unwrap();
+    fs::write(dir.join("card_features.ts"), feature_index_typescript()).unwrap();
+
+    println!("exported to {}", dir.display());
+}
@@ -0,0 +1,19 @@
+use scraping_sample::report::FeatureReport;
+use std::path::Path;
+
+// cargo run --example feature_report -- [cache_dir] [--json]
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let as_json: bool = args.iter().any(|a| a == "--json");
+    let dir: String = args.into_iter()
+        .find(|a| !a.starts_with("--"))
+        .unwrap_or("./text_cache".into());
+
+    let report = FeatureReport::from_cache_dir(Path::new(&dir));
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&report).unwrap());
+    } else {
+        println!("{}", report);
+    }
+}
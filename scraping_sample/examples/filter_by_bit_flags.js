@@ -1,42 +1,34 @@
-const skill_group1 = {
-    doubleCrush: Math.pow(2, 1),
-    lancer: Math.pow(2, 2),
-    banish: Math.pow(2, 3)
-};
+// card_features.json は `cargo run --example export_feature_index -- examples/feature_index` で生成する
+const index = require('./feature_index/card_features.json');
 
-const skill_group2 = {
-    relay: Math.pow(2, 1),
-    buyback: Math.pow(2, 2),
-    discard: Math.pow(2, 3)
-};
+const toWords = (names) => names.reduce((words, name) => {
+    const f = index[name];
+    words[f.word] |= BigInt(f.b);
+    return words;
+}, [0n, 0n]);
 
+// FeatureSet をシリアライズした decimal ([下位ワード, 上位ワード])
 const items = [
     {
         name: "taro",
-        skills1: 10,
-        skills2: 5
+        features: ["17592186044417", "0"]
     }, {
         name: "jiro",
-        skills1: 11,
-        skills2: 2
+        features: ["1025", "0"]
     }
-]
+];
 
-const search_conditions = {
-    skill1: [skill_group1.doubleCrush, skill_group1.banish],
-    skill2: [skill_group2.buyback]
-}
+const search_conditions = ["DoubleCrush", "Damage"];
 
 const search = (_items, conditions) => {
-    const condition_local1 = conditions.skill1.reduce((flag, flag_) => flag | flag_, 0);
-    const condition_local2 = conditions.skill2.reduce((flag, flag_) => flag | flag_, 0);
+    const condition = toWords(conditions);
 
     return _items.filter(item => {
-        return (item.skills1 & condition_local1) === condition_local1
-            && (item.skills2 & condition_local2) === condition_local2;
+        const words = item.features.map(w => BigInt(w));
+        return condition.every((c, i) => (words[i] & c) === c);
     });
 };
 
 
 const filtered = search(items, search_conditions);
-console.log(filtered);
\ No newline at end of file
+console.log(filtered);
@@ -140,7 +140,7 @@ fn main() {
 
 "#.into();
 
-    let arts = Arts::from_source(source);
+    let arts = Arts::from_source(source).unwrap();
     println!("{}", &arts);
     let card: Card = arts.into();
     // println!("{}", card);
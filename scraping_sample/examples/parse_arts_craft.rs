@@ -135,7 +135,7 @@ fn main() {
 
 "#.into();
 
-    let arts = ArtsCraft::from_source(source);
+    let arts = ArtsCraft::from_source(source).unwrap();
     println!("{}", &arts);
     let card: Card = arts.into();
     // println!("{}", card);
@@ -113,7 +113,7 @@ fn main() {
 
 "#.into();
 
-    let key = Key::from_source(source);
+    let key = Key::from_source(source).unwrap();
     // let card: Card = piece.into();
     // println!("{}", Into::<Card>::into(piece));
     println!("{}", &key);
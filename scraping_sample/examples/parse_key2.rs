@@ -136,6 +136,6 @@ fn main() {
     </html>
 
 "#.into();
-    let card = Card::card_from_html(&source);
+    let card = Card::card_from_html(&source).unwrap();
     println!("{}", serde_json::to_string_pretty(&card).unwrap());
 }
\ No newline at end of file
@@ -120,7 +120,7 @@ fn main() {
 
 "#.into();
 
-    let lrig = Lrig::from_source(source);
+    let lrig = Lrig::from_source(source).unwrap();
     println!("{}", &lrig);
     let card: Card = lrig.into();
     // println!("{}", card);
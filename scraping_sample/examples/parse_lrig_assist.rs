@@ -113,7 +113,7 @@ fn main() {
 
 "#.into();
 
-    let lrig = LrigAssist::from_source(source);
+    let lrig = LrigAssist::from_source(source).unwrap();
     println!("{}", &lrig);
     let card: Card = lrig.into();
     // println!("{}", card);
@@ -140,7 +140,7 @@ fn main() {
     </html>
     "#.into();
 
-    let piece = Piece::from_source(source);
+    let piece = Piece::from_source(source).unwrap();
     // let card: Card = piece.into();
     // println!("{}", Into::<Card>::into(piece));
     println!("{}", &piece);
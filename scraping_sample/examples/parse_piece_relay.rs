@@ -124,7 +124,7 @@ fn main() {
     </html>
     "#.into();
 
-    let piece = PieceRelay::from_source(source);
+    let piece = PieceRelay::from_source(source).unwrap();
     // let card: Card = piece.into();
     println!("{}", piece);
 }
\ No newline at end of file
@@ -148,7 +148,7 @@ fn main() {
 
 "#.into();
 
-    let card = Card::card_from_html(&source);
+    let card = Card::card_from_html(&source).unwrap();
     println!("{}", serde_json::to_string_pretty(&card).unwrap());
-    assert_eq!(card.unwrap().card_type, CardType::Resona);
+    assert_eq!(card.card_type, CardType::Resona);
 }
@@ -122,7 +122,7 @@ fn main() {
 
 "#.into();
 
-    let resona = ResonaCraft::from_source(source);
+    let resona = ResonaCraft::from_source(source).unwrap();
     println!("{}", &resona);
     let card: Card = resona.into();
     assert_eq!(card.card_type, CardType::ResonaCraft);
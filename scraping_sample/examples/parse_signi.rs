@@ -120,7 +120,7 @@ fn main() {
 
 "#.into();
 
-    let signi = Signi::from_source(source);
+    let signi = Signi::from_source(source).unwrap();
     println!("{}", &signi);
     let card: Card = signi.into();
     // println!("{}", card);
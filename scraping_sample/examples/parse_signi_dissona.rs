@@ -125,6 +125,6 @@ fn main() {
 
 "#.into();
 
-    let signi = Signi::from_source(source);
+    let signi = Signi::from_source(source).unwrap();
     println!("{}", &signi);
 }
@@ -122,7 +122,7 @@ fn main() {
 
 "#.into();
 
-    let spell = SpellCraft::from_source(source);
+    let spell = SpellCraft::from_source(source).unwrap();
     println!("{}", &spell);
     let card: Card = spell.into();
     // println!("{}", card);
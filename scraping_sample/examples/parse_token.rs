@@ -106,7 +106,7 @@ fn main() {
 
 "#.into();
 
-    let token = Token::from_source(source);
+    let token = Token::from_source(source).unwrap();
     println!("{}", &token);
     let card: Card = token.into();
     // println!("{}", card);
@@ -1,19 +1,13 @@
 use scraping_sample::wixoss::constants::CardFeature;
-use std::collections::HashSet;
+use scraping_sample::wixoss::feature_set::FeatureSet;
 
 fn main() {
-    let feature_set: HashSet<CardFeature> = vec![
+    let feature_set: FeatureSet = vec![
         CardFeature::DoubleCrush,
         CardFeature::Damage,
     ].into_iter().collect();
 
-    println!("{:b}", hashset_to_flags(feature_set));
+    println!("{:b}", feature_set);
+    println!("{:?}", feature_set.to_hex_pair());
+    println!("{}", serde_json::to_string(&feature_set).unwrap());
 }
-
-fn hashset_to_flags(feature_set: HashSet<CardFeature>) -> u64 {
-    let mut b: u64 = 0;
-    feature_set.iter().for_each(|f| {
-        b |= f.to_bit()
-    });
-    b
-}
\ No newline at end of file
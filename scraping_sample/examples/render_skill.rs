@@ -0,0 +1,23 @@
+use scraper::{Html, Selector};
+use scraping_sample::wixoss::skill_text::{RenderMode, SkillRenderer};
+
+// cargo run --example render_skill -- <card.html> [plain|tokens|markdown|html]
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let path: &str = args.first().map(|s| s.as_str()).unwrap_or("tests/fixtures/signi/WXDi-P12-071.html");
+    let mode: RenderMode = match args.get(1).map(|s| s.as_str()) {
+        Some("tokens") => RenderMode::Tokens,
+        Some("markdown") => RenderMode::Markdown,
+        Some("html") => RenderMode::Html,
+        _ => RenderMode::Plain,
+    };
+
+    let source: String = std::fs::read_to_string(path).expect("failed to read card html");
+    let document: Html = Html::parse_document(&source);
+    let selector_card_skill = Selector::parse(".cardSkill").unwrap();
+
+    let renderer = SkillRenderer::new(mode).icon_dir("icons");
+    for element in document.select(&selector_card_skill) {
+        println!("{}", renderer.render(element.inner_html().trim()));
+    }
+}
@@ -4,7 +4,7 @@ use scraping_sample::{
 
 #[tokio::main]
 async fn main() {
-    let cq: CardQuery = CardQuery::from_card_no("WXDi-P14-001".into());
+    let cq: CardQuery = CardQuery::from_card_no("WXDi-P14-001".parse().unwrap());
     let text: Option<String> = cq.download_card_detail("./text_cache").await;
     println!("{}", text.unwrap_or("detail download error".into()))
 }
\ No newline at end of file
@@ -120,7 +120,7 @@ fn main() {
 
 "#.into();
 
-    let spell = Spell::from_source(source);
+    let spell = Spell::from_source(source).unwrap();
     println!("{}", &spell);
     let card: Card = spell.into();
     // println!("{}", card);
@@ -0,0 +1,166 @@
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use serde::Serialize;
+use serde_json::Value;
+use crate::read_cached_card_details;
+use crate::products::ProductCatalog;
+use crate::wixoss::Card;
+use crate::wixoss::schema::CARD_SCHEMA_VERSION;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    JsonLines,
+    Csv,
+    // 全カードを一つのJSONにまとめる
+    Bundle,
+    // 正規化したテーブルにする。ファイルにしか書き出せない(crate::sqlite)
+    Sqlite,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "jsonl" => Ok(ExportFormat::JsonLines),
+            "csv" => Ok(ExportFormat::Csv),
+            "json" | "bundle" => Ok(ExportFormat::Bundle),
+            "sqlite" => Ok(ExportFormat::Sqlite),
+            _ => Err(format!("unknown export format: {} (jsonl, csv, json, sqlite)", s)),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ParseFailure {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct CardPool {
+    pub cards: Vec<Card>,
+    pub failures: Vec<ParseFailure>,
+}
+
+impl CardPool {
+    // キャッシュ内のカード詳細ページをすべてパースする。パースできなかったページは理由とともに失敗として記録して続ける
+    pub fn from_cache_dir(dir: &Path) -> Self {
+        let (details, unreadable) = read_cached_card_details(dir);
+        let mut pool = Self { failures: unreadable, ..Self::default() };
+
+        for (path, html) in details {
+            match Card::card_from_html(&html) {
+                Ok(card) => pool.cards.push(card),
+                Err(e) => pool.failures.push(ParseFailure { path, reason: e.to_string() }),
+            }
+        }
+
+        pool
+    }
+
+    // 指定した商品に収録されているカードだけにする
+    pub fn retain_product(&mut self, catalog: &ProductCatalog, product_code: &str) {
+        self.cards.retain(|card| catalog.contains(product_code, card.card_no()));
+    }
+
+    pub fn write<W: Write>(&self, format: ExportFormat, out: &mut W) -> std::io::Result<()> {
+        match format {
+            ExportFormat::JsonLines => write_json_lines(&self.cards, out),
+            ExportFormat::Csv => write_csv(&self.cards, out),
+            ExportFormat::Bundle => write_bundle(&self.cards, out),
+            ExportFormat::Sqlite => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "sqlite export needs a file path (--out)",
+            )),
+        }
+    }
+
+    pub fn write_failures<W: Write>(&self, out: &mut W) -> std::io::Result<()> {
+        for failure in &self.failures {
+            serde_json::to_writer(&mut *out, failure)?;
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn write_json_lines<W: Write>(cards: &[Card], out: &mut W) -> std::io::Result<()> {
+    for card in cards {
+        serde_json::to_writer(&mut *out, card)?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct Bundle<'a> {
+    schema_version: &'a str,
+    count: usize,
+    cards: &'a [Card],
+}
+
+pub fn write_bundle<W: Write>(cards: &[Card], out: &mut W) -> std::io::Result<()> {
+    let bundle = Bundle { schema_version: CARD_SCHEMA_VERSION, count: cards.len(), cards };
+    serde_json::to_writer_pretty(&mut *out, &bundle)?;
+    writeln!(out)
+}
+
+// 文字列として書き出す列。値はCardのJSON表現から取る
+const CSV_TEXT_COLUMNS: [&str; 14] = [
+    "no", "name", "pronounce", "card_type", "klass", "color", "level",
+    "cost", "limit", "power", "user", "format", "rarity", "is_dissona",
+];
+
+// フィーチャーはビットマスク(FeatureSetの10進数表現、下位・上位ワード)とラベルの両方で出す。
+// 複数の値を持つ列は | で、スキルの各行は改行で区切る
+pub fn write_csv<W: Write>(cards: &[Card], out: &mut W) -> std::io::Result<()> {
+    let mut header: Vec<&str> = CSV_TEXT_COLUMNS.to_vec();
+    header.extend(["time", "story", "feature_bits_low", "feature_bits_high", "features", "life_burst", "skill"]);
+    writeln!(out, "{}", header.join(","))?;
+
+    for card in cards {
+        let json: Value = serde_json::to_value(card).unwrap();
+
+        let mut row: Vec<String> = CSV_TEXT_COLUMNS.iter()
+            .map(|column| match &json[column] {
+                Value::String(s) => s.clone(),
+                Value::Null => String::new(),
+                v => v.to_string(),
+            })
+            .collect();
+
+        row.push(join_strings(&json["time"], "|"));
+        row.push(join_strings(&json["story"], "|"));
+
+        let (low, high) = card.feature_set().to_decimal_pair();
+        row.push(low);
+        row.push(high);
+        row.push(card.feature_set().iter().map(|f| f.label()).collect::<Vec<&str>>().join("|"));
+
+        // 改行を含みうるテキストは最後の列にする
+        row.push(json["life_burst"]["text"].as_str().unwrap_or("").to_string());
+        row.push(join_strings(&json["skill"], "\n"));
+
+        writeln!(out, "{}", row.iter().map(|v| csv_escape(v)).collect::<Vec<String>>().join(","))?;
+    }
+    Ok(())
+}
+
+fn join_strings(value: &Value, separator: &str) -> String {
+    value.as_array()
+        .map(|values| values.iter()
+            .map(|v| v.as_str().map(|s| s.to_string()).unwrap_or_else(|| v.to_string()))
+            .collect::<Vec<String>>()
+            .join(separator))
+        .unwrap_or_default()
+}
+
+fn csv_escape(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
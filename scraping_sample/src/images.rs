@@ -0,0 +1,181 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use reqwest::{Client, StatusCode};
+use sha2::{Digest, Sha256};
+use crate::wixoss::Card;
+
+// カード画像のキャッシュ。テキストのキャッシュと並べて置く(既定は ./text_cache/images)
+//     objects/ab/abcdef....jpg   画像本体。ファイル名は内容のSHA-256
+//     urls/<URLのSHA-256>         そのURLから取得した objects 内のパス
+//     partial/<URLのSHA-256>.part 取得途中のデータ。次回はRangeで続きから取得する
+// 同じ画像が別のURL(再録など)で配られていても、本体は一つだけになる
+pub const IMAGE_CACHE_DIR: &str = "./text_cache/images";
+
+#[derive(Debug)]
+pub enum ImageError {
+    Http(reqwest::Error),
+    Status(StatusCode),
+    Io(std::io::Error),
+}
+
+impl Display for ImageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageError::Http(e) => write!(f, "{}", e),
+            ImageError::Status(status) => write!(f, "unexpected status: {}", status),
+            ImageError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ImageError {}
+
+impl From<reqwest::Error> for ImageError {
+    fn from(e: reqwest::Error) -> Self {
+        ImageError::Http(e)
+    }
+}
+
+impl From<std::io::Error> for ImageError {
+    fn from(e: std::io::Error) -> Self {
+        ImageError::Io(e)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ImageCache {
+    root: PathBuf,
+}
+
+impl ImageCache {
+    pub fn new(root: &Path) -> Self {
+        Self { root: root.to_path_buf() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn url_key(url: &str) -> String {
+        hex::encode(Sha256::digest(url.as_bytes()))
+    }
+
+    fn url_index_path(&self, url: &str) -> PathBuf {
+        self.root.join("urls").join(Self::url_key(url))
+    }
+
+    fn partial_path(&self, url: &str) -> PathBuf {
+        self.root.join("partial").join(format!("{}.part", Self::url_key(url)))
+    }
+
+    // 取得済みならその画像のパス
+    pub fn cached_path(&self, url: &str) -> Option<PathBuf> {
+        let object = fs::read_to_string(self.url_index_path(url)).ok()?;
+        let path = self.root.join(object.trim());
+        path.exists().then_some(path)
+    }
+
+    // 取得済みならそれを返し、無ければ取得して保存する
+    pub async fn fetch(&self, client: &Client, url: &str) -> Result<PathBuf, ImageError> {
+        if let Some(path) = self.cached_path(url) {
+            return Ok(path);
+        }
+
+        let partial = self.partial_path(url);
+        fs::create_dir_all(partial.parent().unwrap())?;
+        let resume_from: u64 = fs::metadata(&partial).map(|m| m.len()).unwrap_or(0);
+
+        let mut request = client.get(url);
+        if resume_from > 0 {
+            request = request.header(reqwest::header::RANGE, format!("bytes={}-", resume_from));
+        }
+        let mut response = request.send().await?;
+
+        // Rangeに対応していないサーバーは最初から返すので、その場合は書き直す
+        let append = match response.status() {
+            StatusCode::PARTIAL_CONTENT => true,
+            StatusCode::OK => false,
+            // 取得途中のデータがすでに全体だった
+            StatusCode::RANGE_NOT_SATISFIABLE if resume_from > 0 => return self.store_partial(url, &partial),
+            status => return Err(ImageError::Status(status)),
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(&partial)?;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk)?;
+        }
+        file.flush()?;
+
+        self.store_partial(url, &partial)
+    }
+
+    // 取得し終えたデータを内容のハッシュの名前で objects へ移す
+    fn store_partial(&self, url: &str, partial: &Path) -> Result<PathBuf, ImageError> {
+        let bytes = fs::read(partial)?;
+        let hash = hex::encode(Sha256::digest(&bytes));
+
+        let extension = Path::new(url.split(['?', '#']).next().unwrap_or(url))
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("img")
+            .to_lowercase();
+        let object = format!("objects/{}/{}.{}", &hash[..2], hash, extension);
+        let path = self.root.join(&object);
+
+        if path.exists() {
+            fs::remove_file(partial)?;
+        } else {
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::rename(partial, &path)?;
+        }
+
+        let index = self.url_index_path(url);
+        fs::create_dir_all(index.parent().unwrap())?;
+        fs::write(index, &object)?;
+
+        Ok(path)
+    }
+
+    // objects 内の画像のパスを、キャッシュの置き場所に依らない objects/… の形にする
+    fn object_key(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.root).unwrap_or(path).to_path_buf()
+    }
+
+    // カードに記録された画像のパス。記録が無ければNone
+    pub fn local_path(&self, card: &Card) -> Option<PathBuf> {
+        card.image()
+            .and_then(|image| image.local_path.as_ref())
+            .map(|key| self.root.join(key))
+    }
+
+    // 取得済みの画像があればカードに記録する。ネットワークには出ない
+    pub fn attach(&self, card: &mut Card) -> bool {
+        let path = card.image().and_then(|image| self.cached_path(&image.url));
+        match path {
+            Some(path) => {
+                card.set_image_path(&self.object_key(&path));
+                true
+            }
+            None => false,
+        }
+    }
+
+    // カードの画像を取得して、保存した場所をカードに記録する。画像の無いカードはNone
+    pub async fn fetch_card_image(&self, client: &Client, card: &mut Card) -> Result<Option<PathBuf>, ImageError> {
+        let Some(url) = card.image().map(|image| image.url.clone()) else {
+            return Ok(None);
+        };
+
+        let path = self.fetch(client, &url).await?;
+        card.set_image_path(&self.object_key(&path));
+        Ok(Some(path))
+    }
+}
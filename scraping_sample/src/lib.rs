@@ -4,18 +4,35 @@ use scraper::{Html, Selector};
 use std::{fs};
 use std::fmt::Display;
 use std::path::{Path, PathBuf};
-use std::fs::{File, ReadDir};
+use std::fs::File;
 use std::io::prelude::*;
 use async_recursion::async_recursion;
 use serde::Deserialize;
+use crate::wixoss::card_no::CardNo;
+use crate::listing::{Listing, ListingPage};
+use crate::export::ParseFailure;
 
 pub mod wixoss;
-
+pub mod report;
+pub mod export;
+pub mod sqlite;
+pub mod images;
+pub mod thumbnails;
+pub mod listing;
+pub mod products;
+
+// 公式の一覧ページ(card_list.php)へPOSTする検索フォーム。
+// 送るのは商品の一覧の取得で使ってきた項目(search, keyword, product_type, product_no, card_page, card_kind, rarelity)だけ。
+// 色やレベルの絞り込みは、実際のフォームの項目名と値を確かめてから足すこと
+// (項目名を間違えるとサーバーは黙って無視し、絞り込まれていない一覧が返る)。
+// card_kind の値はフォームに表示される日本語のまま送る("シグニ"、"スペル" など)
 #[derive(Clone)]
 pub struct SearchQuery {
+    // 一覧ページのURL。テストでは手元のサーバーに差し替える
+    endpoint: String,
     search: String,
     keyword: String,
-    product_type: ProductType,
+    product_type: Option<ProductType>,
     card_page: String,
     card_kind: String,
     rarelity: String,
@@ -38,44 +55,103 @@ impl ProductType {
             ProductType::SpecialCard => String::from("special"),
         }
     }
+
+    // 商品のコード。プロモーションカードと特殊カードはまとめて一つの商品として扱う
+    pub fn code(&self) -> String {
+        match self {
+            ProductType::Booster(product_no) => product_no.clone(),
+            ProductType::Starter(product_no) => product_no.clone(),
+            ProductType::PromotionCard => String::from("promotion"),
+            ProductType::SpecialCard => String::from("special"),
+        }
+    }
 }
 
+const CARD_LIST_URL: &str = "https://www.takaratomy.co.jp/products/wixoss/card/card_list.php";
+
+// 一覧ページ1枚あたりのカード数
+const CARDS_PER_PAGE: i32 = 21;
+
 impl SearchQuery {
     fn new(product_type: &ProductType, card_page: i32) -> SearchQuery {
         SearchQuery {
+            endpoint: CARD_LIST_URL.into(),
             search: "".into(),
             keyword: "".into(),
-            product_type: product_type.clone(),
+            product_type: Some(product_type.clone()),
             card_page: card_page.to_string(),
             card_kind: "".into(),
             rarelity: "".into(),
         }
     }
 
+    // 商品を絞らない検索。条件は以下のメソッドで足していく
+    //     SearchQuery::any_product().rarity("LR").fetch_links().await
+    pub fn any_product() -> SearchQuery {
+        SearchQuery {
+            endpoint: CARD_LIST_URL.into(),
+            search: "".into(),
+            keyword: "".into(),
+            product_type: None,
+            card_page: "1".into(),
+            card_kind: "".into(),
+            rarelity: "".into(),
+        }
+    }
+
+    pub fn in_product(product_type: &ProductType) -> SearchQuery {
+        SearchQuery { product_type: Some(product_type.clone()), ..Self::any_product() }
+    }
+
+    pub fn keyword(mut self, keyword: &str) -> Self {
+        self.keyword = keyword.into();
+        self
+    }
+
+    // 一覧のフォームのカード種類(シグニ、スペルなど)
+    pub fn card_kind(mut self, card_kind: &str) -> Self {
+        self.card_kind = card_kind.into();
+        self
+    }
+
+    pub fn rarity(mut self, rarity: &str) -> Self {
+        self.rarelity = rarity.into();
+        self
+    }
+
+    pub fn page(mut self, card_page: i32) -> Self {
+        self.card_page = card_page.to_string();
+        self
+    }
+
+    pub fn endpoint(mut self, url: &str) -> Self {
+        self.endpoint = url.into();
+        self
+    }
+
     fn get_product_type(&self) -> String {
         match &self.product_type {
-            ProductType::Booster(_product_no) => "booster".into(),
-            ProductType::Starter(_product_no) => "starter".into(),
-            ProductType::PromotionCard => "-".into(),
-            ProductType::SpecialCard => "-".into(),
+            Some(ProductType::Booster(_product_no)) => "booster".into(),
+            Some(ProductType::Starter(_product_no)) => "starter".into(),
+            Some(ProductType::PromotionCard) => "-".into(),
+            Some(ProductType::SpecialCard) => "-".into(),
+            None => "".into(),
         }
     }
 
-    fn to_hashmap(&self) -> HashMap<String, String> {
-        let empty_product_no = String::from("");
-
+    // 公式の一覧ページへPOSTするフォームの内容
+    pub fn to_hashmap(&self) -> HashMap<String, String> {
         let product_no = match &self.product_type {
-            ProductType::Booster(product_no) => product_no,
-            ProductType::Starter(product_no) => product_no,
-            ProductType::PromotionCard => &empty_product_no,
-            ProductType::SpecialCard => &empty_product_no,
+            Some(ProductType::Booster(product_no)) => product_no.clone(),
+            Some(ProductType::Starter(product_no)) => product_no.clone(),
+            _ => String::from(""),
         };
 
         HashMap::from_iter(vec![
             ("search".into(), self.search.clone()),
             ("keyword".into(), self.keyword.clone()),
             ("product_type".into(), self.get_product_type()),
-            ("product_no".into(), product_no.clone()),
+            ("product_no".into(), product_no),
             ("card_page".into(), self.card_page.clone()),
             ("card_kind".into(), self.card_kind.clone()),
             ("rarelity".into(), self.rarelity.clone()),
@@ -83,22 +159,70 @@ impl SearchQuery {
     }
 
     fn to_filename(&self) -> String {
-        format!("{}/p{}.html", &self.product_type.get_path_relative(), &self.card_page)
+        let dir = match &self.product_type {
+            Some(product_type) => product_type.get_path_relative(),
+            None => String::from("search"),
+        };
+        format!("{}/p{}.html", dir, &self.card_page)
     }
 
     fn cache_check(&self, dir: String) -> Result<String, std::io::Error> {
         let path: PathBuf = PathBuf::from(format!("{}/{}", dir, &self.to_filename()));
         if path.exists() {
-            println!("cache found");
+            eprintln!("cache found");
             let mut file: File = File::open(&path)?;
             let mut contents = String::new();
             file.read_to_string(&mut contents)?;
             Ok(contents)
         } else {
-            println!("cache not found");
+            eprintln!("cache not found");
             Err(std::io::Error::new(std::io::ErrorKind::Other, "An unexpected error occurred."))
         }
     }
+
+    async fn fetch_page(&self) -> Result<String, reqwest::Error> {
+        let client: Client = Client::new();
+        let res: Response = client.post(&self.endpoint)
+            .header(reqwest::header::COOKIE, "wixAge=conf;")
+            .form(&self.to_hashmap())
+            .send().await?;
+
+        res.text().await
+    }
+
+    // 検索結果のすべてのページを取得する。
+    // 検索結果は新弾で変わるのでキャッシュはしない
+    pub async fn fetch_listing(&self) -> Result<Listing, reqwest::Error> {
+        let first: ListingPage = ListingPage::from_html(&self.fetch_page().await?);
+        let count: i32 = first.total.unwrap_or(0) as i32;
+        let pages: i32 = (count + CARDS_PER_PAGE - 1) / CARDS_PER_PAGE;
+
+        let mut parsed: Vec<ListingPage> = vec![first];
+        for card_page in 2..=pages {
+            parsed.push(ListingPage::from_html(&self.clone().page(card_page).fetch_page().await?));
+        }
+
+        Ok(Listing::from_pages(parsed))
+    }
+
+    // 検索結果のカード詳細へのリンク
+    pub async fn fetch_links(&self) -> Result<Vec<String>, reqwest::Error> {
+        let listing: Listing = self.fetch_listing().await?;
+        if let Err(e) = listing.validate() {
+            eprintln!("{}", e);
+        }
+        Ok(listing.links())
+    }
+}
+
+// 一覧ページ中のカード詳細へのリンク
+pub fn find_card_links(content: &str) -> Vec<String> {
+    let parsed_html: Html = Html::parse_document(content);
+    let selector: Selector = Selector::parse("a.c-box").unwrap();
+    parsed_html.select(&selector).map(|element| {
+        element.value().attr("href")
+            .unwrap_or("").to_owned()
+    }).filter(|href| !href.is_empty()).collect()
 }
 
 pub fn try_mkdir(rel_path: &Path) -> Result<(), std::io::Error> {
@@ -109,62 +233,71 @@ pub fn try_mkdir(rel_path: &Path) -> Result<(), std::io::Error> {
     Ok(())
 }
 
+// 商品の一覧ページをキャッシュできなかった理由
+#[derive(Debug)]
+pub enum ProductIndexError {
+    Request(reqwest::Error),
+    Io(std::io::Error),
+    // 取得したページに一覧(.cardDip)が無い。キャッシュするはずだったファイル名
+    ListingNotFound(String),
+}
+
+impl Display for ProductIndexError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProductIndexError::Request(e) => write!(f, "{}", e),
+            ProductIndexError::Io(e) => write!(f, "{}", e),
+            ProductIndexError::ListingNotFound(file) => write!(f, "card list (.cardDip) not found for {}", file),
+        }
+    }
+}
+
+impl std::error::Error for ProductIndexError {}
+
+impl From<reqwest::Error> for ProductIndexError {
+    fn from(e: reqwest::Error) -> Self {
+        ProductIndexError::Request(e)
+    }
+}
+
+impl From<std::io::Error> for ProductIndexError {
+    fn from(e: std::io::Error) -> Self {
+        ProductIndexError::Io(e)
+    }
+}
+
 #[async_recursion]
-pub async fn cache_product_index(product_type: &ProductType, card_page: i32) -> Result<(), reqwest::Error> {
+pub async fn cache_product_index(product_type: &ProductType, card_page: i32) -> Result<(), ProductIndexError> {
     let p_no = product_type.get_path_relative();
-    println!("{} {}", p_no, card_page);
-
-    let url = "https://www.takaratomy.co.jp/products/wixoss/card/card_list.php";
+    eprintln!("{} {}", p_no, card_page);
 
     let search_query: SearchQuery = SearchQuery::new(product_type, card_page);
 
-    let main: Option<String> = match search_query.cache_check("./text_cache".to_string()) {
-        Ok(content_) => {
-            Some(content_)
-        }
+    let main: String = match search_query.cache_check("./text_cache".to_string()) {
+        Ok(content_) => content_,
         _ => {
-            let form: HashMap<String, String> = search_query.to_hashmap();
-
-            let client: Client = Client::new();
-            let res: Response = client.post(url)
-                .header(reqwest::header::COOKIE, "wixAge=conf;")
-                .form(&form)
-                .send().await?;
-
-            let body: String = res.text().await.unwrap();
+            let body: String = search_query.fetch_page().await?;
 
             let cache_filename: PathBuf = PathBuf::from(format!("./text_cache/{}", &search_query.to_filename()));
+            let content = find_one(&body, ".cardDip".into())
+                .ok_or_else(|| ProductIndexError::ListingNotFound(search_query.to_filename()))?;
 
             if let Some(parent_path) = cache_filename.parent() {
-                try_mkdir(parent_path).unwrap();
-
-                let content = find_one(&body, ".cardDip".into());
-
-                if let Some(element) = &content {
-                    let file: Result<File, std::io::Error> = File::create(&cache_filename);
-                    if let Ok(mut file_) = file {
-                        file_.write_all(element.as_bytes()).unwrap();
-                    }
-                }
-                content
-            } else {
-                None
+                try_mkdir(parent_path)?;
             }
+            File::create(&cache_filename)?.write_all(content.as_bytes())?;
+            content
         }
     };
 
-    if let Some(count) = find_one(&main.unwrap(), "h3 p span".into()) {
-        let count = extract_number(&count);
-
-        if let Some(count) = count {
-            let pages = (count / 21) + 1;
+    if let Some(count) = ListingPage::from_html(&main).total {
+        let pages = (count as i32 + CARDS_PER_PAGE - 1) / CARDS_PER_PAGE;
 
-            if card_page < pages {
-                cache_product_index(product_type, card_page + 1).await.unwrap();
-            }
+        if card_page < pages {
+            cache_product_index(product_type, card_page + 1).await?;
         }
     } else {
-        println!("not found");
+        eprintln!("not found");
     }
 
 
@@ -175,45 +308,78 @@ pub fn find_one(content: &str, selector: String) -> Option<String> {
     let document: Html = Html::parse_document(content);
     let main_selector: Selector = Selector::parse(selector.as_str()).unwrap();
 
-    println!("{:?}", document.clone());
     document.select(&main_selector).next().map(|element| element.inner_html())
 }
 
-pub async fn collect_card_detail_links(product_type: &ProductType) -> Result<Vec<String>, ()> {
+// cache_product_index でキャッシュした一覧ページを読む
+pub fn read_cached_listing(product_type: &ProductType) -> std::io::Result<Listing> {
     let product_root: String = product_type.get_path_relative();
     let path_s: String = format!("./text_cache/{}", product_root);
     let product_dir: &Path = Path::new(&path_s);
 
-    println!("{}", product_dir.display());
-
-    try_mkdir(product_dir).unwrap();
-    let files_result: std::io::Result<ReadDir> = fs::read_dir(product_dir);
-
-    match files_result {
-        Ok(files) => {
-            let all_text: String = files.into_iter().map(|f| {
-                let p: fs::DirEntry = f.unwrap();
-                let file_path: PathBuf = p.path();
-                let content: String = fs::read_to_string(file_path).unwrap();
+    eprintln!("{}", product_dir.display());
 
-                content
-            }).collect::<Vec<_>>().join("");
+    try_mkdir(product_dir)?;
+    // ページの取りこぼしなどで件数が合わなくても、集められた分は返す。確かめるのは呼び出し側(validate)
+    Listing::from_cache_dir(product_dir)
+}
 
-            let parsed_html: Html = Html::parse_document(&all_text);
-            let selector: Selector = Selector::parse("a.c-box").unwrap();
-            let links: Vec<String> = parsed_html.select(&selector).map(|element| {
-                element.value().attr("href")
-                    .unwrap_or("").to_owned()
-            }).filter(|href| !href.is_empty()).collect();
-            Ok(links)
+pub async fn collect_card_detail_links(product_type: &ProductType) -> Result<Vec<String>, ()> {
+    match read_cached_listing(product_type) {
+        Ok(listing) => {
+            if let Err(e) = listing.validate() {
+                eprintln!("{}", e);
+            }
+            Ok(listing.links())
         }
         Err(err) => {
-            println!("{:?}", err);
+            eprintln!("{:?}", err);
             Err(())
         }
     }
 }
 
+// キャッシュディレクトリを再帰的に辿り、カード詳細ページ(.cardDetailの中身)だけを集める。
+// 読めなかったファイルやディレクトリ(I/Oエラー、UTF-8でない)は、パース失敗と同じ形で理由とともに返す
+pub fn read_cached_card_details(dir: &Path) -> (Vec<(PathBuf, String)>, Vec<ParseFailure>) {
+    let mut details: Vec<(PathBuf, String)> = Vec::new();
+    let mut failures: Vec<ParseFailure> = Vec::new();
+    collect_card_details(dir, &mut details, &mut failures);
+
+    details.sort_by(|a, b| a.0.cmp(&b.0));
+    failures.sort_by(|a, b| a.path.cmp(&b.path));
+    (details, failures)
+}
+
+fn collect_card_details(dir: &Path, details: &mut Vec<(PathBuf, String)>, failures: &mut Vec<ParseFailure>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            failures.push(ParseFailure { path: dir.to_path_buf(), reason: e.to_string() });
+            return;
+        }
+    };
+
+    for entry in entries {
+        let path: PathBuf = match entry {
+            Ok(entry) => entry.path(),
+            Err(e) => {
+                failures.push(ParseFailure { path: dir.to_path_buf(), reason: e.to_string() });
+                continue;
+            }
+        };
+        if path.is_dir() {
+            collect_card_details(&path, details, failures);
+        } else if path.extension().is_some_and(|ext| ext == "html") {
+            match fs::read_to_string(&path) {
+                Ok(content) if content.contains("class=\"cardData\"") => details.push((path, content)),
+                Ok(_) => {}
+                Err(e) => failures.push(ParseFailure { path, reason: e.to_string() }),
+            }
+        }
+    }
+}
+
 #[allow(dead_code)]
 pub fn find_many(content: &str, selector: String) -> Vec<String> {
     let document: Html = Html::parse_document(content);
@@ -234,20 +400,15 @@ pub fn extract_number(s: &str) -> Option<i32> {
 #[derive(Debug, Deserialize)]
 pub struct CardQuery {
     card: String,
-    card_no: String,
+    card_no: CardNo,
 }
 
 impl CardQuery {
     pub fn get_relative_filename(&self) -> String {
-        let mut tokens: Vec<_> = self.card_no.split('-').collect();
-        let id = tokens.last().unwrap().to_string();
-        tokens.pop();
-        let dir: String = tokens.join("-");
-
-        format!("{}/{}.html", dir, id)
+        self.card_no.relative_cache_path()
     }
 
-    pub fn from_card_no(card_no: String) -> Self {
+    pub fn from_card_no(card_no: CardNo) -> Self {
         Self {
             card_no,
             card: "card_detail".into(),
@@ -255,7 +416,7 @@ impl CardQuery {
     }
     pub fn to_hashmap(&self) -> HashMap<String, String> {
         HashMap::from_iter(vec![
-            ("card_no".into(), self.card_no.clone()),
+            ("card_no".into(), self.card_no.to_string()),
             ("card".into(), self.card.clone()),
         ])
     }
@@ -263,7 +424,7 @@ impl CardQuery {
     pub async fn download_card_detail(&self, cache_dir: &'static str) -> Option<String> {
         let cache_file: PathBuf = PathBuf::from(format!("{}/{}", cache_dir, self.get_relative_filename()));
 
-        println!("{:?}", cache_file);
+        eprintln!("{:?}", cache_file);
         if cache_file.exists() {
             let mut file: File = File::open(&cache_file).expect("cache file open error");
             let mut contents = String::new();
@@ -295,7 +456,7 @@ impl CardQuery {
                             _ => None
                         }
                     } else {
-                        println!("{}", body);
+                        eprintln!("{}", body);
                         None
                     }
                 }
@@ -0,0 +1,170 @@
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+use scraper::{ElementRef, Html, Selector};
+use serde::Serialize;
+use crate::extract_number;
+use crate::wixoss::card_no::CardNo;
+
+// 一覧ページ(cache_product_index がキャッシュする .cardDip の中身)の1枚分
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ListingEntry {
+    pub card_no: CardNo,
+    pub name: Option<String>,
+    pub rarity: Option<String>,
+    // 一覧に出ている縮小画像のURL
+    pub thumbnail: Option<String>,
+    // カード詳細ページへのリンク(href のまま)
+    pub link: String,
+}
+
+impl ListingEntry {
+    // カード番号はリンクのクエリ(card_no=...)から、無ければ .cardNum から取る
+    fn from_element(element: ElementRef) -> Option<Self> {
+        let link = element.value().attr("href").filter(|href| !href.is_empty())?.to_string();
+
+        let card_no: CardNo = card_no_from_link(&link)
+            .or_else(|| select_text(&element, ".cardNum"))
+            .and_then(|no| no.parse().ok())?;
+
+        let selector_img = Selector::parse("img").unwrap();
+        let img = element.select(&selector_img).next();
+
+        let name = select_text(&element, ".cardName")
+            .or_else(|| img.and_then(|img| img.value().attr("alt")).map(|alt| alt.trim().to_string()))
+            .filter(|name| !name.is_empty());
+
+        Some(Self {
+            card_no,
+            name,
+            rarity: select_text(&element, ".cardRarity"),
+            thumbnail: img.and_then(|img| img.value().attr("src")).map(|src| src.to_string()),
+            link,
+        })
+    }
+}
+
+fn card_no_from_link(link: &str) -> Option<String> {
+    link.split(['?', '&'])
+        .find_map(|pair| pair.strip_prefix("card_no="))
+        .map(|no| no.to_string())
+}
+
+fn select_text(element: &ElementRef, selector: &str) -> Option<String> {
+    let selector = Selector::parse(selector).unwrap();
+    element.select(&selector).next()
+        .map(|e| e.text().collect::<String>().trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ListingPage {
+    // h3 p span の件数。商品・検索条件全体の件数で、このページの件数ではない
+    pub total: Option<u32>,
+    pub entries: Vec<ListingEntry>,
+    // カード番号を読めなかったリンク
+    pub unparsed: Vec<String>,
+}
+
+impl ListingPage {
+    pub fn from_html(html: &str) -> Self {
+        let document: Html = Html::parse_document(html);
+
+        let selector_count = Selector::parse("h3 p span").unwrap();
+        let total: Option<u32> = document.select(&selector_count).next()
+            .and_then(|span| extract_number(&span.inner_html()))
+            .and_then(|count| u32::try_from(count).ok());
+
+        let selector_link = Selector::parse("a.c-box").unwrap();
+        let mut page = Self { total, ..Self::default() };
+        for element in document.select(&selector_link) {
+            match ListingEntry::from_element(element) {
+                Some(entry) => page.entries.push(entry),
+                None => page.unparsed.push(element.value().attr("href").unwrap_or("").to_string()),
+            }
+        }
+
+        page
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListingError {
+    MissingTotal,
+    CountMismatch { expected: u32, found: usize, unparsed: usize },
+}
+
+impl Display for ListingError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListingError::MissingTotal => write!(f, "total count (h3 p span) not found"),
+            ListingError::CountMismatch { expected, found, unparsed } => write!(
+                f, "listing has {} cards but {} entries were collected ({} links without a card number)",
+                expected, found, unparsed
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ListingError {}
+
+// 一つの商品(または検索条件)の全ページ分
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Listing {
+    pub total: Option<u32>,
+    pub entries: Vec<ListingEntry>,
+    pub unparsed: Vec<String>,
+}
+
+impl Listing {
+    pub fn from_pages(pages: impl IntoIterator<Item = ListingPage>) -> Self {
+        let mut listing = Self::default();
+        for page in pages {
+            listing.total = listing.total.or(page.total);
+            listing.entries.extend(page.entries);
+            listing.unparsed.extend(page.unparsed);
+        }
+        listing
+    }
+
+    // キャッシュした p1.html, p2.html, ... をページ順に読む
+    pub fn from_cache_dir(dir: &Path) -> std::io::Result<Self> {
+        let mut pages: Vec<(u32, PathBuf)> = fs::read_dir(dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter_map(|path| {
+                let stem = path.file_stem()?.to_str()?;
+                let page: u32 = stem.strip_prefix('p')?.parse().ok()?;
+                (path.extension()? == "html").then_some((page, path))
+            })
+            .collect();
+        pages.sort();
+
+        let mut parsed: Vec<ListingPage> = Vec::new();
+        for (_, path) in pages {
+            parsed.push(ListingPage::from_html(&fs::read_to_string(path)?));
+        }
+        Ok(Self::from_pages(parsed))
+    }
+
+    // カード番号を読めなかったリンクも、詳細ページへのリンクとして残す
+    pub fn links(&self) -> Vec<String> {
+        self.entries.iter()
+            .map(|entry| entry.link.clone())
+            .chain(self.unparsed.iter().filter(|href| !href.is_empty()).cloned())
+            .collect()
+    }
+
+    // 集めた件数が一覧の件数と合っているか。ページの取りこぼしやパースの失敗を検出する
+    pub fn validate(&self) -> Result<(), ListingError> {
+        let expected = self.total.ok_or(ListingError::MissingTotal)?;
+        if expected as usize != self.entries.len() {
+            return Err(ListingError::CountMismatch {
+                expected,
+                found: self.entries.len(),
+                unparsed: self.unparsed.len(),
+            });
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,450 @@
+use std::fs::File;
+use std::future::Future;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use scraping_sample::{cache_product_index, parse_card_url, read_cached_listing, CardQuery, ProductType, SearchQuery};
+use scraping_sample::export::{CardPool, ExportFormat};
+use scraping_sample::sqlite::export_sqlite;
+use scraping_sample::images::{ImageCache, IMAGE_CACHE_DIR};
+use scraping_sample::products::{is_release_date, ProductCatalog, PRODUCT_CATALOG_FILE_NAME};
+use scraping_sample::thumbnails::{ThumbnailFormat, ThumbnailSize, Thumbnailer, THUMBNAIL_DIR};
+use scraping_sample::wixoss::Card;
+use scraping_sample::wixoss::card_no::CardNo;
+
+const USAGE: &str = "usage:
+    scraping_sample crawl <product> [--starter]      (product: WXDi-P14 など / promotion / special)
+    scraping_sample product <code> [--name NAME] [--release-date YYYY-MM-DD]   (crawl 済みの商品に名前・発売日を入れる)
+    scraping_sample search [--keyword TEXT] [--kind KIND] [--rarity RARITY] [--product CODE [--starter]]
+    scraping_sample fetch <card_no|url> [--json]
+    scraping_sample parse <file> [--json]
+    scraping_sample images [--cache DIR] [--images DIR]
+    scraping_sample thumbnails [--cache DIR] [--images DIR] [--out DIR] [--format webp|jpeg] [--sizes small=160,medium=320]
+    scraping_sample export [--cache DIR] [--images DIR] [--format jsonl|csv|json|sqlite] [--product CODE] [--out FILE] [--failures FILE]
+    scraping_sample features <card_no> [--cache DIR]
+
+exit status: 0 成功, 1 取得・パース・書き出しの失敗, 2 引数の誤り";
+
+// crawl, fetch はサイトのキャッシュをここに置く(cache_product_index が固定で使っている)
+const CACHE_DIR: &str = "./text_cache";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let rest: &[String] = args.get(1..).unwrap_or_default();
+
+    match args.first().map(|s| s.as_str()) {
+        Some("crawl") => crawl(rest),
+        Some("product") => product(rest),
+        Some("search") => search(rest),
+        Some("fetch") => fetch(rest),
+        Some("parse") => parse(rest),
+        Some("images") => images(rest),
+        Some("thumbnails") => thumbnails(rest),
+        Some("export") => export(rest),
+        Some("features") => features(rest),
+        _ => usage_error(None),
+    }
+}
+
+fn usage_error(message: Option<&str>) -> ExitCode {
+    if let Some(message) = message {
+        eprintln!("{}", message);
+    }
+    eprintln!("{}", USAGE);
+    ExitCode::from(2)
+}
+
+// --name value 形式のオプションを取り出す
+fn option<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|a| a == name)
+        .and_then(|i| args.get(i + 1))
+        .map(|s| s.as_str())
+}
+
+fn flag(args: &[String], name: &str) -> bool {
+    args.iter().any(|a| a == name)
+}
+
+// 最初の位置引数。オプションの値は飛ばす
+fn positional(args: &[String]) -> Option<&str> {
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--cache" | "--images" | "--format" | "--out" | "--failures" | "--sizes"
+            | "--keyword" | "--kind" | "--rarity" | "--product"
+            | "--name" | "--release-date" => { iter.next(); }
+            a if a.starts_with("--") => {}
+            a => return Some(a),
+        }
+    }
+    None
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Runtime::new().expect("failed to start tokio runtime").block_on(future)
+}
+
+fn print_card(card: &Card, as_json: bool) {
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(card).unwrap());
+    } else {
+        println!("{}", card);
+    }
+}
+
+fn crawl(args: &[String]) -> ExitCode {
+    let product_type = match positional(args) {
+        Some("promotion") => ProductType::PromotionCard,
+        Some("special") => ProductType::SpecialCard,
+        Some(product) if flag(args, "--starter") => ProductType::Starter(product.into()),
+        Some(product) => ProductType::Booster(product.into()),
+        None => return usage_error(Some("crawl needs a product code")),
+    };
+
+    let downloaded = block_on(async {
+        if let Err(e) = cache_product_index(&product_type, 1).await {
+            eprintln!("failed to fetch the card list: {}", e);
+            return None;
+        }
+        let listing = match read_cached_listing(&product_type) {
+            Ok(listing) => listing,
+            Err(e) => {
+                eprintln!("failed to read the card list: {}", e);
+                return None;
+            }
+        };
+
+        // 一覧に載っているカードを商品に紐づけておく(再録も含む)
+        let catalog_path = Path::new(CACHE_DIR).join(PRODUCT_CATALOG_FILE_NAME);
+        let saved = ProductCatalog::load(&catalog_path).and_then(|mut catalog| {
+            catalog.add_listing(&product_type, &listing);
+            catalog.save(&catalog_path)
+        });
+        if let Err(e) = &saved {
+            eprintln!("failed to update {}: {}", catalog_path.display(), e);
+        }
+
+        let mut failed: Vec<String> = Vec::new();
+        for entry in &listing.entries {
+            let query = CardQuery::from_card_no(entry.card_no.clone());
+            if query.download_card_detail(CACHE_DIR).await.is_none() {
+                failed.push(entry.card_no.to_string());
+            }
+        }
+        Some((listing, failed, saved.is_ok()))
+    });
+
+    match downloaded {
+        Some((listing, failed, catalog_saved)) => {
+            let total = listing.entries.len();
+            eprintln!("cached {} of {} cards", total - failed.len(), total);
+            for card_no in &failed {
+                eprintln!("\t{}", card_no);
+            }
+
+            // 一覧の件数と合わない(ページの取りこぼしや、カード番号を読めないリンクがある)ときも失敗にする
+            let validated = listing.validate();
+            if let Err(e) = &validated {
+                eprintln!("{}", e);
+                for href in &listing.unparsed {
+                    eprintln!("\tunparsed link: {}", href);
+                }
+            }
+            if failed.is_empty() && validated.is_ok() && catalog_saved { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+        }
+        None => ExitCode::FAILURE,
+    }
+}
+
+// 商品名・発売日を products.json に記録する。一覧ページからは取れない
+fn product(args: &[String]) -> ExitCode {
+    let Some(code) = positional(args) else {
+        return usage_error(Some("product needs a product code"));
+    };
+    let name = option(args, "--name");
+    let release_date = option(args, "--release-date");
+    if name.is_none() && release_date.is_none() {
+        return usage_error(Some("product needs --name or --release-date"));
+    }
+    if release_date.is_some_and(|date| !is_release_date(date)) {
+        return usage_error(Some("--release-date must be YYYY-MM-DD"));
+    }
+
+    let catalog_path = Path::new(CACHE_DIR).join(PRODUCT_CATALOG_FILE_NAME);
+    let mut catalog = match ProductCatalog::load(&catalog_path) {
+        Ok(catalog) => catalog,
+        Err(e) => {
+            eprintln!("failed to read {}: {}", catalog_path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if !catalog.describe(code, name, release_date) {
+        eprintln!("{} is not in {} (run `crawl` first)", code, catalog_path.display());
+        return ExitCode::FAILURE;
+    }
+    if let Err(e) = catalog.save(&catalog_path) {
+        eprintln!("failed to update {}: {}", catalog_path.display(), e);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+// 検索条件に合うカード詳細へのリンクを1行ずつ出す
+fn search(args: &[String]) -> ExitCode {
+    let mut query = match option(args, "--product") {
+        Some(product) if flag(args, "--starter") => SearchQuery::in_product(&ProductType::Starter(product.into())),
+        Some(product) => SearchQuery::in_product(&ProductType::Booster(product.into())),
+        None => SearchQuery::any_product(),
+    };
+    if let Some(keyword) = option(args, "--keyword") {
+        query = query.keyword(keyword);
+    }
+    if let Some(card_kind) = option(args, "--kind") {
+        query = query.card_kind(card_kind);
+    }
+    if let Some(rarity) = option(args, "--rarity") {
+        query = query.rarity(rarity);
+    }
+
+    match block_on(query.fetch_links()) {
+        Ok(links) => {
+            for link in &links {
+                println!("{}", link);
+            }
+            eprintln!("found {} cards", links.len());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("search failed: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn fetch(args: &[String]) -> ExitCode {
+    let query: CardQuery = match positional(args) {
+        Some(url) if url.starts_with("http://") || url.starts_with("https://") => match parse_card_url(url) {
+            Ok(query) => query,
+            Err(e) => return usage_error(Some(&format!("not a card detail url: {}", e))),
+        },
+        Some(card_no) => match card_no.parse::<CardNo>() {
+            Ok(card_no) => CardQuery::from_card_no(card_no),
+            Err(e) => return usage_error(Some(&e.to_string())),
+        },
+        None => return usage_error(Some("fetch needs a card number or url")),
+    };
+
+    let Some(html) = block_on(query.download_card_detail(CACHE_DIR)) else {
+        eprintln!("failed to fetch the card detail");
+        return ExitCode::FAILURE;
+    };
+
+    match Card::card_from_html(&html) {
+        Ok(card) => {
+            print_card(&card, flag(args, "--json"));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse(args: &[String]) -> ExitCode {
+    let Some(path) = positional(args) else {
+        return usage_error(Some("parse needs a file"));
+    };
+    let html = match std::fs::read_to_string(path) {
+        Ok(html) => html,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    // JSONは共通のCard、テキストは種類ごとの表示
+    let printed = if flag(args, "--json") {
+        Card::card_from_html(&html).map(|card| print_card(&card, true))
+    } else {
+        Card::describe_html(&html).map(|text| println!("{}", text))
+    };
+
+    match printed {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn features(args: &[String]) -> ExitCode {
+    let card_no: CardNo = match positional(args).map(|s| s.parse()) {
+        Some(Ok(card_no)) => card_no,
+        Some(Err(e)) => return usage_error(Some(&e.to_string())),
+        None => return usage_error(Some("features needs a card number")),
+    };
+    let cache_dir: &str = option(args, "--cache").unwrap_or(CACHE_DIR);
+
+    let path: PathBuf = Path::new(cache_dir).join(card_no.relative_cache_path());
+    let html = match std::fs::read_to_string(&path) {
+        Ok(html) => html,
+        Err(_) => {
+            eprintln!("{} is not cached (try `fetch {}`)", path.display(), card_no);
+            return ExitCode::FAILURE;
+        }
+    };
+    let card = match Card::card_from_html(&html) {
+        Ok(card) => card,
+        Err(e) => {
+            eprintln!("{}: {}", path.display(), e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let (low, high) = card.feature_set().to_decimal_pair();
+    println!("{} bits: {} {}", card.no(), low, high);
+    for feature in card.feature_set().iter() {
+        println!("{}\t{}", feature.label(), feature.category());
+    }
+    for detail in card.feature_details() {
+        println!("{}\t{}\t{:?}", detail.feature.label(), detail.amount, detail.target);
+    }
+
+    ExitCode::SUCCESS
+}
+
+// キャッシュ内の全カードの画像を取得する。取得済みのものは飛ばす
+fn images(args: &[String]) -> ExitCode {
+    let cache_dir: &str = option(args, "--cache").unwrap_or(CACHE_DIR);
+    let images = ImageCache::new(Path::new(option(args, "--images").unwrap_or(IMAGE_CACHE_DIR)));
+
+    let mut pool = CardPool::from_cache_dir(Path::new(cache_dir));
+
+    let failed: Vec<(String, String)> = block_on(async {
+        let client = reqwest::Client::new();
+        let mut failed: Vec<(String, String)> = Vec::new();
+        for card in pool.cards.iter_mut() {
+            if let Err(e) = images.fetch_card_image(&client, card).await {
+                failed.push((card.no().to_string(), e.to_string()));
+            }
+        }
+        failed
+    });
+
+    let stored = pool.cards.iter().filter(|card| card.image().is_some_and(|image| image.local_path.is_some())).count();
+    eprintln!("{} of {} card images cached in {}", stored, pool.cards.len(), images.root().display());
+    for (card_no, reason) in &failed {
+        eprintln!("\t{}: {}", card_no, reason);
+    }
+
+    if failed.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
+
+// 取得済みのカード画像から縮小画像を作る。作成済みのものは飛ばす
+fn thumbnails(args: &[String]) -> ExitCode {
+    let cache_dir: &str = option(args, "--cache").unwrap_or(CACHE_DIR);
+    let images = ImageCache::new(Path::new(option(args, "--images").unwrap_or(IMAGE_CACHE_DIR)));
+
+    let mut thumbnailer = Thumbnailer::new(Path::new(option(args, "--out").unwrap_or(THUMBNAIL_DIR)));
+    if let Some(format) = option(args, "--format") {
+        match format.parse::<ThumbnailFormat>() {
+            Ok(format) => thumbnailer = thumbnailer.format(format),
+            Err(e) => return usage_error(Some(&e)),
+        }
+    }
+    if let Some(sizes) = option(args, "--sizes") {
+        match ThumbnailSize::parse_list(sizes) {
+            Ok(sizes) => thumbnailer = thumbnailer.sizes(sizes),
+            Err(e) => return usage_error(Some(&e)),
+        }
+    }
+
+    let mut pool = CardPool::from_cache_dir(Path::new(cache_dir));
+
+    let mut generated = 0;
+    let mut missing = 0;
+    let mut failed: Vec<(String, String)> = Vec::new();
+    for card in pool.cards.iter_mut() {
+        if !images.attach(card) {
+            missing += 1;
+            continue;
+        }
+        match thumbnailer.generate_for_card(card, &images) {
+            Ok(_) => generated += 1,
+            Err(e) => failed.push((card.no().to_string(), e.to_string())),
+        }
+    }
+
+    eprintln!("thumbnails for {} cards, {} without a cached image (run `images` first)", generated, missing);
+    for (card_no, reason) in &failed {
+        eprintln!("\t{}: {}", card_no, reason);
+    }
+
+    if failed.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE }
+}
+
+fn export(args: &[String]) -> ExitCode {
+    let cache_dir: &str = option(args, "--cache").unwrap_or(CACHE_DIR);
+    let format: ExportFormat = match option(args, "--format").unwrap_or("jsonl").parse() {
+        Ok(format) => format,
+        Err(e) => return usage_error(Some(&e)),
+    };
+
+    if format == ExportFormat::Sqlite && option(args, "--out").is_none() {
+        return usage_error(Some("--format sqlite needs --out FILE"));
+    }
+
+    let catalog = match ProductCatalog::load(&Path::new(cache_dir).join(PRODUCT_CATALOG_FILE_NAME)) {
+        Ok(catalog) => catalog,
+        Err(e) => {
+            eprintln!("failed to read the product catalog: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut pool = CardPool::from_cache_dir(Path::new(cache_dir));
+    if let Some(product) = option(args, "--product") {
+        pool.retain_product(&catalog, product);
+    }
+
+    // 取得済みの画像の場所をカードに入れる
+    if let Some(dir) = option(args, "--images") {
+        let images = ImageCache::new(Path::new(dir));
+        for card in pool.cards.iter_mut() {
+            images.attach(card);
+        }
+    }
+
+    let written = match option(args, "--out") {
+        Some(path) if format == ExportFormat::Sqlite => {
+            block_on(export_sqlite(&pool.cards, &catalog, Path::new(path))).map_err(std::io::Error::other)
+        }
+        Some(path) => File::create(path).and_then(|file| {
+            let mut out = BufWriter::new(file);
+            pool.write(format, &mut out)?;
+            out.flush()
+        }),
+        None => pool.write(format, &mut std::io::stdout().lock()),
+    };
+    if let Err(e) = written {
+        eprintln!("export failed: {}", e);
+        return ExitCode::FAILURE;
+    }
+
+    // パースに失敗したページは出力とは別に報告する
+    if let Some(path) = option(args, "--failures") {
+        if let Err(e) = File::create(path).and_then(|mut file| pool.write_failures(&mut file)) {
+            eprintln!("failed to write failures: {}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+    eprintln!("exported {} cards, {} failures", pool.cards.len(), pool.failures.len());
+    for failure in &pool.failures {
+        eprintln!("\t{}: {}", failure.path.display(), failure.reason);
+    }
+
+    ExitCode::SUCCESS
+}
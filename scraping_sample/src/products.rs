@@ -0,0 +1,161 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use crate::ProductType;
+use crate::listing::Listing;
+use crate::wixoss::card_no::CardNo;
+
+// 商品(ブースター、構築済みデッキなど)と、そこに収録されたカードの対応。
+// 再録があるので、カードと商品は多対多になる。クロール時に一覧ページから集めて
+// キャッシュディレクトリの products.json に保存する
+pub const PRODUCT_CATALOG_FILE_NAME: &str = "products.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProductKind {
+    Booster,
+    Starter,
+    Promotion,
+    Special,
+}
+
+impl ProductKind {
+    // products.json に書き出す名前と同じ
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProductKind::Booster => "Booster",
+            ProductKind::Starter => "Starter",
+            ProductKind::Promotion => "Promotion",
+            ProductKind::Special => "Special",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Product {
+    pub code: String,
+    pub kind: ProductKind,
+    // 名前と発売日は一覧ページに無いので、`product` コマンド(ProductCatalog::describe)で入れる
+    pub name: Option<String>,
+    // YYYY-MM-DD
+    pub release_date: Option<String>,
+    // 一覧ページの件数
+    pub card_count: Option<u32>,
+}
+
+impl From<&ProductType> for Product {
+    fn from(product_type: &ProductType) -> Self {
+        let kind = match product_type {
+            ProductType::Booster(_) => ProductKind::Booster,
+            ProductType::Starter(_) => ProductKind::Starter,
+            ProductType::PromotionCard => ProductKind::Promotion,
+            ProductType::SpecialCard => ProductKind::Special,
+        };
+
+        Self { code: product_type.code(), kind, name: None, release_date: None, card_count: None }
+    }
+}
+
+// release_date として使える日付(YYYY-MM-DD で、実在する日)か
+pub fn is_release_date(date: &str) -> bool {
+    date.len() == 10 && NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok()
+}
+
+// プロモーションカードのカード番号のセット(PR-K060 など)
+const PROMOTION_SET: &str = "PR";
+
+// カード番号から決める商品のコード。プロモーションカードは crawl promotion と同じ一つの商品にまとめる
+fn product_code_from_card_no(card_no: &CardNo) -> String {
+    if card_no.set() == PROMOTION_SET {
+        ProductType::PromotionCard.code()
+    } else {
+        card_no.product_code()
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProductCatalog {
+    products: BTreeMap<String, Product>,
+    // カード番号 -> 収録されている商品のコード
+    cards: BTreeMap<CardNo, BTreeSet<String>>,
+}
+
+impl ProductCatalog {
+    // ファイルが無ければ空
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(std::io::Error::other),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self).unwrap())
+    }
+
+    // 登録済みの商品は、名前・発売日を残して件数だけ更新する
+    pub fn add_listing(&mut self, product_type: &ProductType, listing: &Listing) {
+        let code = product_type.code();
+        let product = self.products.entry(code.clone()).or_insert_with(|| product_type.into());
+        product.card_count = listing.total.or(product.card_count);
+
+        for entry in &listing.entries {
+            self.link(&code, entry.card_no.clone());
+        }
+    }
+
+    // 名前や発売日を更新する。収録カードの対応はそのまま
+    pub fn upsert(&mut self, product: Product) {
+        self.products.insert(product.code.clone(), product);
+    }
+
+    // 登録済みの商品に名前・発売日を入れる。Noneの項目はそのまま。未登録の商品ならfalse
+    pub fn describe(&mut self, code: &str, name: Option<&str>, release_date: Option<&str>) -> bool {
+        let Some(product) = self.products.get_mut(code) else {
+            return false;
+        };
+        if let Some(name) = name {
+            product.name = Some(name.into());
+        }
+        if let Some(release_date) = release_date {
+            product.release_date = Some(release_date.into());
+        }
+        true
+    }
+
+    pub fn link(&mut self, product_code: &str, card_no: CardNo) {
+        self.cards.entry(card_no).or_default().insert(product_code.into());
+    }
+
+    pub fn product(&self, code: &str) -> Option<&Product> {
+        self.products.get(code)
+    }
+
+    pub fn products(&self) -> impl Iterator<Item = &Product> {
+        self.products.values()
+    }
+
+    // カードが収録されている商品のコード。クロールしていないカードはカード番号から決める
+    pub fn product_codes_of(&self, card_no: &CardNo) -> Vec<String> {
+        match self.cards.get(card_no) {
+            Some(codes) => codes.iter().cloned().collect(),
+            None => vec![product_code_from_card_no(card_no)],
+        }
+    }
+
+    pub fn cards_of(&self, product_code: &str) -> Vec<&CardNo> {
+        self.cards.iter()
+            .filter(|(_, codes)| codes.contains(product_code))
+            .map(|(card_no, _)| card_no)
+            .collect()
+    }
+
+    pub fn contains(&self, product_code: &str, card_no: &CardNo) -> bool {
+        self.product_codes_of(card_no).iter().any(|code| code == product_code)
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use crate::read_cached_card_details;
+use crate::wixoss::{skill_lines_from_html, Card, CardType};
+use crate::wixoss::constants::CardFeature;
+
+#[derive(Debug, Serialize)]
+pub struct UnclassifiedLine {
+    pub line: String,
+    pub count: usize,
+    pub cards: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct FeatureReport {
+    pub total_cards: usize,
+    pub parse_failures: Vec<PathBuf>,
+    pub feature_counts: BTreeMap<CardFeature, usize>,
+    // 他のカードに与える能力から検出されたフィーチャー
+    pub granted_feature_counts: BTreeMap<CardFeature, usize>,
+    // ライフバーストを持つカードの数と、その効果の内訳
+    pub life_burst_count: usize,
+    pub life_burst_feature_counts: BTreeMap<CardFeature, usize>,
+    pub card_type_counts: BTreeMap<CardType, usize>,
+    pub card_type_features: BTreeMap<CardType, BTreeMap<CardFeature, usize>>,
+    // スキルテキストがあるのにフィーチャーが一つも検出されなかったカード
+    pub featureless_cards: Vec<String>,
+    // フィーチャーが一つも検出されなかったスキル行(出現回数の多い順)
+    pub unclassified_lines: Vec<UnclassifiedLine>,
+}
+
+impl FeatureReport {
+    pub fn from_cache_dir(dir: &Path) -> Self {
+        let mut report = Self::default();
+        let mut unclassified: HashMap<String, Vec<String>> = HashMap::new();
+
+        let (details, unreadable) = read_cached_card_details(dir);
+        report.parse_failures.extend(unreadable.into_iter().map(|failure| failure.path));
+
+        for (path, html) in details {
+            let card: Card = match Card::card_from_html(&html) {
+                Ok(card) => card,
+                Err(_) => {
+                    report.parse_failures.push(path);
+                    continue;
+                }
+            };
+
+            report.total_cards += 1;
+            *report.card_type_counts.entry(card.card_type.clone()).or_insert(0) += 1;
+
+            let per_type = report.card_type_features.entry(card.card_type.clone()).or_default();
+            for feature in card.features() {
+                *report.feature_counts.entry(*feature).or_insert(0) += 1;
+                *per_type.entry(*feature).or_insert(0) += 1;
+            }
+            for feature in card.granted_skills().iter().flat_map(|a| a.features.iter()) {
+                *report.granted_feature_counts.entry(*feature).or_insert(0) += 1;
+            }
+            if let Some(life_burst) = card.life_burst() {
+                report.life_burst_count += 1;
+                for feature in &life_burst.features {
+                    *report.life_burst_feature_counts.entry(*feature).or_insert(0) += 1;
+                }
+            }
+
+            let lines = skill_lines_from_html(&html);
+            if !lines.is_empty() && card.features().is_empty() {
+                report.featureless_cards.push(card.no().to_string());
+            }
+
+            for (line, features) in lines {
+                if features.is_empty() {
+                    unclassified.entry(line).or_default().push(card.no().to_string());
+                }
+            }
+        }
+
+        let mut unclassified_lines: Vec<UnclassifiedLine> = unclassified
+            .into_iter()
+            .map(|(line, cards)| UnclassifiedLine { line, count: cards.len(), cards })
+            .collect();
+        unclassified_lines.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.line.cmp(&b.line)));
+        report.unclassified_lines = unclassified_lines;
+
+        report
+    }
+}
+
+impl Display for FeatureReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "カード数\t:{}", self.total_cards)?;
+        writeln!(f, "パース失敗\t:{}", self.parse_failures.len())?;
+        for path in &self.parse_failures {
+            writeln!(f, "\t{}", path.display())?;
+        }
+
+        let mut feature_counts: Vec<(&CardFeature, &usize)> = self.feature_counts.iter().collect();
+        feature_counts.sort_by(|a, b| b.1.cmp(a.1));
+
+        writeln!(f, "\n[フィーチャー別]")?;
+        for (feature, count) in feature_counts {
+            writeln!(f, "{}\t{}", count, feature)?;
+        }
+
+        writeln!(f, "\n[付与される能力のフィーチャー]")?;
+        for (feature, count) in &self.granted_feature_counts {
+            writeln!(f, "{}\t{}", count, feature)?;
+        }
+
+        writeln!(f, "\n[ライフバースト]({})", self.life_burst_count)?;
+        for (feature, count) in &self.life_burst_feature_counts {
+            writeln!(f, "{}\t{}", count, feature)?;
+        }
+
+        writeln!(f, "\n[カード種類別]")?;
+        for (card_type, count) in &self.card_type_counts {
+            let features: Vec<String> = self.card_type_features.get(card_type)
+                .map(|m| m.iter().map(|(feature, c)| format!("{}:{}", feature, c)).collect())
+                .unwrap_or_default();
+            writeln!(f, "{}\t{}\t{}", card_type, count, features.join(", "))?;
+        }
+
+        writeln!(f, "\n[フィーチャーなし]({})", self.featureless_cards.len())?;
+        for no in &self.featureless_cards {
+            writeln!(f, "{}", no)?;
+        }
+
+        writeln!(f, "\n[未分類のスキル行]({})", self.unclassified_lines.len())?;
+        for line in &self.unclassified_lines {
+            writeln!(f, "{}\t{}", line.count, line.line)?;
+        }
+        write!(f, "")
+    }
+}
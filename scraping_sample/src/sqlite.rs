@@ -0,0 +1,211 @@
+use std::path::Path;
+use sqlx::{Connection, Sqlite, SqliteConnection, Transaction};
+use sqlx::sqlite::SqliteConnectOptions;
+use crate::wixoss::Card;
+use crate::wixoss::constants::CardFeature;
+use crate::products::ProductCatalog;
+
+// デッキビルダーのオフライン用に、パース済みのカードをSQLiteファイルへ書き出す。
+// フィーチャーのビット(CardFeature::to_bit)は64bitずつのワードに分けてINTEGERで持つ。
+// SQLiteのINTEGERは符号付きなので最上位ビットは負の値になるが、& での判定はそのまま使える
+//     select no from cards c join features f on f.name = 'Draw'
+//     where (case f.word when 0 then c.feature_bits_low else c.feature_bits_high end) & f.bit != 0;
+
+const SCHEMA: [&str; 12] = [
+    "create table cards (
+        no text primary key,
+        name text not null,
+        pronounce text not null,
+        reading_hiragana text not null,
+        reading_romaji text not null,
+        reading_collation text not null,
+        card_type text not null,
+        klass text,
+        color text not null,
+        level integer,
+        cost text,
+        limit_text text,
+        power text,
+        user text,
+        time text,
+        story text,
+        is_dissona integer not null,
+        format text not null,
+        rarity text not null,
+        feature_bits_low integer not null,
+        feature_bits_high integer not null
+    )",
+    "create table skills (
+        id integer primary key autoincrement,
+        card_no text not null references cards(no),
+        position integer not null,
+        kind text not null,
+        text text not null
+    )",
+    "create table features (
+        id integer primary key,
+        name text not null unique,
+        label text not null,
+        category text not null,
+        word integer not null,
+        bit integer not null
+    )",
+    "create table card_features (
+        card_no text not null references cards(no),
+        feature_id integer not null references features(id),
+        primary key (card_no, feature_id)
+    )",
+    "create table products (
+        code text primary key,
+        kind text,
+        name text,
+        release_date text,
+        card_count integer
+    )",
+    "create table product_cards (
+        product_code text not null references products(code),
+        card_no text not null references cards(no),
+        primary key (product_code, card_no)
+    )",
+    "create index idx_cards_name on cards(name)",
+    "create index idx_cards_pronounce on cards(pronounce)",
+    "create index idx_cards_card_type on cards(card_type)",
+    "create index idx_cards_color on cards(color)",
+    "create index idx_cards_level on cards(level)",
+    "create index idx_skills_card_no on skills(card_no)",
+];
+
+// 既存のファイルは作り直す。収録商品はクロール時に集めたものを使い、無ければカード番号から決める
+pub async fn export_sqlite(cards: &[Card], catalog: &ProductCatalog, path: &Path) -> Result<(), sqlx::Error> {
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+
+    let options = SqliteConnectOptions::new().filename(path).create_if_missing(true);
+    let mut conn = SqliteConnection::connect_with(&options).await?;
+    let mut tx = conn.begin().await?;
+
+    for statement in SCHEMA {
+        sqlx::query(statement).execute(&mut *tx).await?;
+    }
+
+    insert_features(&mut tx).await?;
+    insert_products(&mut tx, catalog).await?;
+    for card in cards {
+        insert_card(&mut tx, card, catalog).await?;
+    }
+
+    tx.commit().await?;
+    conn.close().await
+}
+
+async fn insert_features(tx: &mut Transaction<'_, Sqlite>) -> Result<(), sqlx::Error> {
+    for feature in CardFeature::ALL {
+        let position = feature.bit_position();
+        sqlx::query("insert into features (id, name, label, category, word, bit) values (?, ?, ?, ?, ?, ?)")
+            .bind(position as i64)
+            .bind(format!("{:?}", feature))
+            .bind(feature.label())
+            .bind(format!("{:?}", feature.category()))
+            .bind((position / 64) as i64)
+            .bind((1_u64 << (position % 64)) as i64)
+            .execute(&mut **tx)
+            .await?;
+    }
+    Ok(())
+}
+
+async fn insert_products(tx: &mut Transaction<'_, Sqlite>, catalog: &ProductCatalog) -> Result<(), sqlx::Error> {
+    for product in catalog.products() {
+        sqlx::query("insert into products (code, kind, name, release_date, card_count) values (?, ?, ?, ?, ?)")
+            .bind(&product.code)
+            .bind(product.kind.as_str())
+            .bind(&product.name)
+            .bind(&product.release_date)
+            .bind(product.card_count.map(|count| count as i64))
+            .execute(&mut **tx)
+            .await?;
+    }
+    Ok(())
+}
+
+async fn insert_card(tx: &mut Transaction<'_, Sqlite>, card: &Card, catalog: &ProductCatalog) -> Result<(), sqlx::Error> {
+    // 非公開のフィールドはJSON表現から取る
+    let json: serde_json::Value = serde_json::to_value(card).unwrap();
+    let text = |key: &str| json[key].as_str().filter(|s| !s.is_empty()).map(|s| s.to_string());
+    let joined = |key: &str| json[key].as_array()
+        .map(|values| values.iter()
+            .map(|v| v.as_str().map(|s| s.to_string()).unwrap_or_else(|| v.to_string()))
+            .collect::<Vec<String>>()
+            .join("|"))
+        .filter(|s| !s.is_empty());
+
+    let [low, high] = card.feature_set().to_words();
+
+    sqlx::query("insert into cards (no, name, pronounce, reading_hiragana, reading_romaji, reading_collation, card_type, klass, color, level, cost, limit_text, power, user, time, story, is_dissona, format, rarity, feature_bits_low, feature_bits_high) values (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)")
+        .bind(card.no())
+        .bind(text("name").unwrap_or_default())
+        .bind(text("pronounce").unwrap_or_default())
+        .bind(&card.reading().hiragana)
+        .bind(&card.reading().romaji)
+        .bind(&card.reading().collation)
+        .bind(format!("{:?}", card.card_type))
+        .bind(text("klass"))
+        .bind(text("color").unwrap_or_default())
+        .bind(text("level").and_then(|l| l.parse::<i64>().ok()))
+        .bind(text("cost"))
+        .bind(text("limit"))
+        .bind(text("power"))
+        .bind(text("user"))
+        .bind(joined("time"))
+        .bind(joined("story"))
+        .bind(card.is_dissona())
+        .bind(text("format").unwrap_or_default())
+        .bind(text("rarity").unwrap_or_default())
+        .bind(low as i64)
+        .bind(high as i64)
+        .execute(&mut **tx)
+        .await?;
+
+    let skills = json["skill"].as_array().cloned().unwrap_or_default();
+    let mut lines: Vec<(&str, String)> = skills.iter()
+        .filter_map(|s| s.as_str())
+        .map(|s| ("skill", s.to_string()))
+        .collect();
+    lines.extend(card.granted_skills().iter().map(|a| ("granted", a.text.clone())));
+    if let Some(life_burst) = card.life_burst() {
+        lines.push(("life_burst", life_burst.text.clone()));
+    }
+
+    for (position, (kind, line)) in lines.into_iter().enumerate() {
+        sqlx::query("insert into skills (card_no, position, kind, text) values (?, ?, ?, ?)")
+            .bind(card.no())
+            .bind(position as i64)
+            .bind(kind)
+            .bind(line)
+            .execute(&mut **tx)
+            .await?;
+    }
+
+    for feature in card.feature_set().iter() {
+        sqlx::query("insert into card_features (card_no, feature_id) values (?, ?)")
+            .bind(card.no())
+            .bind(feature.bit_position() as i64)
+            .execute(&mut **tx)
+            .await?;
+    }
+
+    for product_code in catalog.product_codes_of(card.card_no()) {
+        sqlx::query("insert or ignore into products (code) values (?)")
+            .bind(&product_code)
+            .execute(&mut **tx)
+            .await?;
+        sqlx::query("insert or ignore into product_cards (product_code, card_no) values (?, ?)")
+            .bind(&product_code)
+            .bind(card.no())
+            .execute(&mut **tx)
+            .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,262 @@
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use image::DynamicImage;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::imageops::FilterType;
+use crate::images::ImageCache;
+use crate::wixoss::{Card, CardParseError};
+use crate::wixoss::card_no::CardNo;
+
+// 画像キャッシュに取得済みのカード画像から、Web UI用の縮小画像を作る。
+// ファイル名はカード番号で決まる: <出力先>/<サイズ名>/WXDi-P12-071.webp
+pub const THUMBNAIL_DIR: &str = "./text_cache/thumbnails";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailFormat {
+    // 可逆圧縮
+    WebP,
+    Jpeg,
+}
+
+impl ThumbnailFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ThumbnailFormat::WebP => "webp",
+            ThumbnailFormat::Jpeg => "jpg",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ThumbnailFormat::WebP => "image/webp",
+            ThumbnailFormat::Jpeg => "image/jpeg",
+        }
+    }
+}
+
+impl FromStr for ThumbnailFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "webp" => Ok(ThumbnailFormat::WebP),
+            "jpeg" | "jpg" => Ok(ThumbnailFormat::Jpeg),
+            _ => Err(format!("unknown thumbnail format: {} (webp, jpeg)", s)),
+        }
+    }
+}
+
+// 幅だけを決める。高さは元画像の縦横比から計算する
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThumbnailSize {
+    pub name: String,
+    pub width: u32,
+}
+
+impl ThumbnailSize {
+    pub fn new(name: &str, width: u32) -> Self {
+        Self { name: name.into(), width }
+    }
+
+    // "small=160,medium=320" の形
+    pub fn parse_list(s: &str) -> Result<Vec<Self>, String> {
+        s.split(',')
+            .map(|size| {
+                let (name, width) = size.split_once('=').ok_or_else(|| format!("invalid size: {}", size))?;
+                let width: u32 = width.parse().ok().filter(|w| *w > 0).ok_or_else(|| format!("invalid width: {}", size))?;
+                Ok(Self::new(name.trim(), width))
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+pub enum ThumbnailError {
+    UnknownSize(String),
+    SourceNotFound(String),
+    Parse(CardParseError),
+    Image(image::ImageError),
+    Io(std::io::Error),
+}
+
+impl Display for ThumbnailError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThumbnailError::UnknownSize(size) => write!(f, "unknown thumbnail size: {}", size),
+            ThumbnailError::SourceNotFound(card_no) => write!(f, "no cached image for {}", card_no),
+            ThumbnailError::Parse(e) => write!(f, "{}", e),
+            ThumbnailError::Image(e) => write!(f, "{}", e),
+            ThumbnailError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ThumbnailError {}
+
+impl From<CardParseError> for ThumbnailError {
+    fn from(e: CardParseError) -> Self {
+        ThumbnailError::Parse(e)
+    }
+}
+
+impl From<image::ImageError> for ThumbnailError {
+    fn from(e: image::ImageError) -> Self {
+        ThumbnailError::Image(e)
+    }
+}
+
+impl From<std::io::Error> for ThumbnailError {
+    fn from(e: std::io::Error) -> Self {
+        ThumbnailError::Io(e)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Thumbnailer {
+    dir: PathBuf,
+    sizes: Vec<ThumbnailSize>,
+    format: ThumbnailFormat,
+    jpeg_quality: u8,
+}
+
+impl Thumbnailer {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            sizes: vec![ThumbnailSize::new("small", 160), ThumbnailSize::new("medium", 320)],
+            format: ThumbnailFormat::WebP,
+            jpeg_quality: 85,
+        }
+    }
+
+    pub fn sizes(mut self, sizes: Vec<ThumbnailSize>) -> Self {
+        self.sizes = sizes;
+        self
+    }
+
+    pub fn format(mut self, format: ThumbnailFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    pub fn jpeg_quality(mut self, jpeg_quality: u8) -> Self {
+        self.jpeg_quality = jpeg_quality.clamp(1, 100);
+        self
+    }
+
+    pub fn get_format(&self) -> ThumbnailFormat {
+        self.format
+    }
+
+    pub fn get_sizes(&self) -> &[ThumbnailSize] {
+        &self.sizes
+    }
+
+    // 設定したサイズだけを受け付ける。名前はそのままパスに使うので、パスを作る前に確かめること
+    fn size(&self, name: &str) -> Result<&ThumbnailSize, ThumbnailError> {
+        self.sizes.iter()
+            .find(|s| s.name == name)
+            .ok_or_else(|| ThumbnailError::UnknownSize(name.into()))
+    }
+
+    pub fn path_for(&self, card_no: &CardNo, size: &str) -> PathBuf {
+        self.dir.join(size).join(format!("{}.{}", card_no, self.format.extension()))
+    }
+
+    // 一つのサイズを作る。すでにあれば作り直さない
+    pub fn generate_size(&self, card_no: &CardNo, source: &Path, size: &str) -> Result<PathBuf, ThumbnailError> {
+        let size = self.size(size)?;
+        let path = self.path_for(card_no, &size.name);
+        if path.exists() {
+            return Ok(path);
+        }
+
+        let image = image::open(source)?;
+        self.write(&resize(&image, size.width), &path)?;
+        Ok(path)
+    }
+
+    // すべてのサイズを作る。元画像の読み込みは一度だけ
+    pub fn generate(&self, card_no: &CardNo, source: &Path) -> Result<Vec<PathBuf>, ThumbnailError> {
+        let mut image: Option<DynamicImage> = None;
+        let mut paths: Vec<PathBuf> = Vec::new();
+
+        for size in &self.sizes {
+            let path = self.path_for(card_no, &size.name);
+            if !path.exists() {
+                if image.is_none() {
+                    image = Some(image::open(source)?);
+                }
+                self.write(&resize(image.as_ref().unwrap(), size.width), &path)?;
+            }
+            paths.push(path);
+        }
+
+        Ok(paths)
+    }
+
+    // カードに記録された画像から作る。画像を取得していないカードはエラー
+    pub fn generate_for_card(&self, card: &Card, images: &ImageCache) -> Result<Vec<PathBuf>, ThumbnailError> {
+        let source = images.local_path(card)
+            .ok_or_else(|| ThumbnailError::SourceNotFound(card.no().into()))?;
+        self.generate(card.card_no(), &source)
+    }
+
+    // Webサーバーからの要求に応じて作る。カード詳細ページのキャッシュから画像のURLを引き、
+    // 画像キャッシュにあるものを使う(ネットワークには出ない)
+    pub fn generate_on_demand(&self, card_no: &CardNo, size: &str, text_cache: &Path, images: &ImageCache) -> Result<PathBuf, ThumbnailError> {
+        let size = &self.size(size)?.name;
+        let path = self.path_for(card_no, size);
+        if path.exists() {
+            return Ok(path);
+        }
+
+        let html = fs::read_to_string(text_cache.join(card_no.relative_cache_path()))
+            .map_err(|_| ThumbnailError::SourceNotFound(card_no.to_string()))?;
+        let mut card = Card::card_from_html(&html)?;
+        if !images.attach(&mut card) {
+            return Err(ThumbnailError::SourceNotFound(card_no.to_string()));
+        }
+
+        let source = images.local_path(&card).unwrap();
+        self.generate_size(card_no, &source, size)
+    }
+
+    // 途中まで書いたファイルを配信しないよう、一時ファイルに書いてから置き換える。
+    // 同じ縮小画像への要求が重なっても互いの一時ファイルを壊さないよう、名前は書くたびに変える
+    fn write(&self, image: &DynamicImage, path: &Path) -> Result<(), ThumbnailError> {
+        fs::create_dir_all(path.parent().unwrap())?;
+        let tmp = path.with_extension(format!("{}.{}.tmp", self.format.extension(), uuid::Uuid::new_v4()));
+
+        {
+            let mut out = BufWriter::new(File::create(&tmp)?);
+            match self.format {
+                ThumbnailFormat::WebP => {
+                    DynamicImage::ImageRgba8(image.to_rgba8()).write_with_encoder(WebPEncoder::new_lossless(&mut out))?
+                }
+                ThumbnailFormat::Jpeg => {
+                    DynamicImage::ImageRgb8(image.to_rgb8()).write_with_encoder(JpegEncoder::new_with_quality(&mut out, self.jpeg_quality))?
+                }
+            }
+        }
+
+        if let Err(e) = fs::rename(&tmp, path) {
+            let _ = fs::remove_file(&tmp);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+}
+
+// 幅を合わせて縦横比を保つ。元より大きくはしない
+fn resize(image: &DynamicImage, width: u32) -> DynamicImage {
+    if image.width() <= width {
+        return image.clone();
+    }
+    let height = ((image.height() as u64 * width as u64) / image.width() as u64).max(1) as u32;
+    image.resize_exact(width, height, FilterType::Lanczos3)
+}
@@ -0,0 +1,196 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use regex::Regex;
+use schemars::JsonSchema;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use serde::{Deserialize, Serialize};
+
+// カード番号。"-"で区切った最初がセット、最後が番号、間がサブコード
+//     WX22-025         セット WX22、番号 025
+//     WXDi-P12-TK01A   セット WXDi、サブコード P12、番号 TK01、バリアント A
+//     WDA-F01-CO-01    セット WDA、サブコード F01-CO、番号 01
+//     PR-K060          セット PR、番号 K060
+// 番号は英字の接頭辞(TK, Kなど)、数字、英字のバリアント(パラレルのP、トークンの表裏A/Bなど)からなる
+// パラレルのバリアント(WXDi-P14-001P)
+const PARALLEL_VARIANT: &str = "P";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct CardNo {
+    text: String,
+    set: String,
+    sub_code: Option<String>,
+    prefix: String,
+    number: u32,
+    digits: String,
+    variant: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardNoError {
+    Empty,
+    InvalidCharacter(String),
+    MissingNumber(String),
+    InvalidNumber(String),
+}
+
+impl Display for CardNoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardNoError::Empty => write!(f, "card number is empty"),
+            CardNoError::InvalidCharacter(s) => write!(f, "invalid character in card number: {}", s),
+            CardNoError::MissingNumber(s) => write!(f, "card number has no number part: {}", s),
+            CardNoError::InvalidNumber(s) => write!(f, "invalid number part in card number: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for CardNoError {}
+
+impl FromStr for CardNo {
+    type Err = CardNoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        if text.is_empty() {
+            return Err(CardNoError::Empty);
+        }
+        if !text.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(CardNoError::InvalidCharacter(text.into()));
+        }
+
+        let segments: Vec<&str> = text.split('-').collect();
+        if segments.len() < 2 || segments.iter().any(|s| s.is_empty()) {
+            return Err(CardNoError::MissingNumber(text.into()));
+        }
+
+        let re_number = Regex::new(r"^([A-Za-z]*)(\d+)([A-Za-z]*)$").unwrap();
+        let last = segments[segments.len() - 1];
+        let captures = re_number.captures(last).ok_or_else(|| CardNoError::InvalidNumber(text.into()))?;
+        let number: u32 = captures[2].parse().map_err(|_| CardNoError::InvalidNumber(text.into()))?;
+
+        let middle = &segments[1..segments.len() - 1];
+
+        Ok(Self {
+            text: text.into(),
+            set: segments[0].into(),
+            sub_code: if middle.is_empty() { None } else { Some(middle.join("-")) },
+            prefix: captures[1].into(),
+            number,
+            digits: captures[2].into(),
+            variant: captures[3].into(),
+        })
+    }
+}
+
+impl TryFrom<String> for CardNo {
+    type Error = CardNoError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<CardNo> for String {
+    fn from(value: CardNo) -> Self {
+        value.text
+    }
+}
+
+impl CardNo {
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn set(&self) -> &str {
+        &self.set
+    }
+
+    pub fn sub_code(&self) -> Option<&str> {
+        self.sub_code.as_deref()
+    }
+
+    // 番号の数値部分。TK01Aなら1
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    pub fn variant(&self) -> Option<&str> {
+        if self.variant.is_empty() { None } else { Some(&self.variant) }
+    }
+
+    // 最後の"-"より前。収録商品のコードで、キャッシュのディレクトリにもなる
+    pub fn product_code(&self) -> String {
+        match &self.sub_code {
+            Some(sub_code) => format!("{}-{}", self.set, sub_code),
+            None => self.set.clone(),
+        }
+    }
+
+    // 最後の"-"より後ろ(TK01Aなど)
+    pub fn number_part(&self) -> String {
+        format!("{}{}{}", self.prefix, self.digits, self.variant)
+    }
+
+    // パラレル(末尾のP)を除いたカード番号。パラレルを元のカードとまとめるのに使う。
+    // トークンの表裏(A/B)などは別のカードなので、パラレル以外のバリアントはそのまま残す
+    pub fn base(&self) -> CardNo {
+        if self.variant != PARALLEL_VARIANT {
+            return self.clone();
+        }
+        let text = self.text[..self.text.len() - self.variant.len()].to_string();
+        Self { text, variant: String::new(), ..self.clone() }
+    }
+
+    // キャッシュ内の詳細ページの位置。以前のキャッシュと同じ "WXDi-P12/071.html" の形
+    pub fn relative_cache_path(&self) -> String {
+        format!("{}/{}.html", self.product_code(), self.number_part())
+    }
+}
+
+// セットとサブコードは英字と数字の並びに分けて、数字は数として比べる(WXDi-P9 < WXDi-P10)
+fn natural_key(s: &str) -> Vec<(Option<u64>, &str)> {
+    let re_runs = Regex::new(r"\d+|\D+").unwrap();
+    re_runs.find_iter(s)
+        .map(|run| (run.as_str().parse::<u64>().ok(), run.as_str()))
+        .collect()
+}
+
+// セット、サブコード、接頭辞、番号(数値として)、バリアントの順に比べる
+impl Ord for CardNo {
+    fn cmp(&self, other: &Self) -> Ordering {
+        natural_key(&self.set).cmp(&natural_key(&other.set))
+            .then_with(|| self.sub_code.as_deref().map(natural_key).cmp(&other.sub_code.as_deref().map(natural_key)))
+            .then_with(|| (&self.prefix, self.number, &self.variant, &self.text)
+                .cmp(&(&other.prefix, other.number, &other.variant, &other.text)))
+    }
+}
+
+impl PartialOrd for CardNo {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for CardNo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl JsonSchema for CardNo {
+    fn schema_name() -> String {
+        "CardNo".into()
+    }
+
+    // スキーマ上はただの文字列として埋め込む
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
@@ -1,5 +1,6 @@
 use std::fmt::{Display, Formatter};
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 #[macro_export]
 macro_rules! features {
@@ -15,236 +16,240 @@ macro_rules! features {
         };
     }
 
-#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize)]
-pub enum CardFeature {
-    DoubleCrush,
-    TripleCrush,
-    DiscardOpponent,
-    RandomDiscard,
-    Draw,
-    Assassin,
-    Freeze,
-    Drop,
-    // DeckAttack,
-    OnDrop,
-    OnRefresh,
-    Lancer,
-    SLancer,
-    Penetrate,
-    NonAttackable,
-    Down,
-    Up,
-    Charge,
-    EnerAttack,
-    Trash,
-    Ener,
-    PowerUp,
-    PowerDown,
-    Bounce,
-    DeckBounce,
-    Salvage,
-    LifeBurst,
-    Shadow,
-    Invulnerable,
-    OnSpell,
-    OnArts,
-    OnPiece,
-    OnBanish,
-    Banish,
-    Guard,
-    OnGuard,
-    AttackNoEffect,
-    // OnAttack,
-    // OnAttackStart,
-    OnTouch,
-    Awake,
-    Exceed,
-    OnExceed,
-    AddLife,
-    OnBurst,
-    LifeTrash,
-    LifeCrush,
-    Damage,
-    OnLifeCrush,
-    Position,
-    Vanilla,
-    Untouchable,
-    // アークゲイン
-    TopCheck,
-    BottomCheck,
-    Barrier,
-    MultiEner,
-    LrigTrash,
-    Charm,
-    Craft,
-    Acce,
-    Rise,
-    Recollect,
-    SeekTop,
-    EraseSkill,
-    CancelDamage,
-    Reanimate,
-    AdditionalAttack,
-    UnGuardable,
-    SalvageSpell,
-    BanishOnAttack
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize)]
+pub enum FeatureCategory {
+    Keyword,
+    Trigger,
+    Removal,
+    Control,
+    Resource,
+    Protection,
+    Offense,
+    Mechanic,
 }
 
-impl Display for CardFeature {
-    #[allow(unreachable_patterns)]
+impl FeatureCategory {
+    pub const ALL: &'static [FeatureCategory] = &[
+        FeatureCategory::Keyword,
+        FeatureCategory::Trigger,
+        FeatureCategory::Removal,
+        FeatureCategory::Control,
+        FeatureCategory::Resource,
+        FeatureCategory::Protection,
+        FeatureCategory::Offense,
+        FeatureCategory::Mechanic,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FeatureCategory::Keyword => "キーワード能力",
+            FeatureCategory::Trigger => "誘発条件",
+            FeatureCategory::Removal => "除去",
+            FeatureCategory::Control => "妨害",
+            FeatureCategory::Resource => "リソース",
+            FeatureCategory::Protection => "防御",
+            FeatureCategory::Offense => "攻撃",
+            FeatureCategory::Mechanic => "メカニクス",
+        }
+    }
+}
+
+impl Display for FeatureCategory {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let label = match self {
-            CardFeature::DoubleCrush => "ダブルクラッシュ",
-            CardFeature::TripleCrush => "トリプルクラッシュ",
-            CardFeature::DiscardOpponent => "手札破壊",
-            CardFeature::RandomDiscard => "ランダム手札破壊",
-            CardFeature::Draw => "ドロー",
-            CardFeature::Assassin => "アサシン",
-            CardFeature::Freeze => "凍結",
-            CardFeature::Drop => "デッキドロップ", // DeckAttack,
-            CardFeature::OnDrop => "デッキドロップ時",
-            CardFeature::OnRefresh => "リフレッシュ時",
-            CardFeature::Lancer => "ランサー",
-            CardFeature::SLancer => "Sランサー",
-            CardFeature::Penetrate => "ガード不可",
-            CardFeature::NonAttackable => "アタック不可",
-            CardFeature::Down => "ダウン",
-            CardFeature::Up => "アップ",
-            CardFeature::Charge => "エナチャージ",
-            CardFeature::EnerAttack => "エナ破壊",
-            CardFeature::Trash => "トラッシュ送り",
-            CardFeature::Ener => "エナ送り",
-            CardFeature::PowerUp => "パワーアップ",
-            CardFeature::PowerDown => "パワーダウン",
-            CardFeature::Bounce => "バウンス",
-            CardFeature::DeckBounce => "デッキバウンス",
-            CardFeature::Salvage => "回収",
-            CardFeature::LifeBurst => "ライフバースト",
-            CardFeature::Shadow => "シャドウ",
-            CardFeature::Invulnerable => "バニッシュされない",
-            CardFeature::OnSpell => "スペル使用時",
-            CardFeature::OnArts => "アーツ使用時",
-            CardFeature::OnPiece => "ピース使用時",
-            CardFeature::OnBanish => "バニッシュした時",
-            CardFeature::Banish => "バニッシュ",
-            CardFeature::Guard => "ガード",
-            CardFeature::OnGuard => "ガードした時",
-            CardFeature::AttackNoEffect => "アタック無効",
-            // CardFeature::OnAttack => "アタック時",
-            // CardFeature::OnAttackStart => "アタック開始時",
-            CardFeature::OnTouch => "対象になった時",
-            CardFeature::Awake => "覚醒",
-            CardFeature::Exceed => "エクシード",
-            CardFeature::OnExceed => "エクシードした時",
-            CardFeature::AddLife => "ライフクロス追加",
-            CardFeature::OnBurst => "ライフバースト発動時",
-            CardFeature::LifeTrash => "ライフクロストラッシュ送り",
-            CardFeature::LifeCrush => "クラッシュ",
-            CardFeature::Damage => "ダメージ",
-            CardFeature::OnLifeCrush => "クラッシュ時",
-            CardFeature::Position => "シグニゾーン移動",
-            CardFeature::Vanilla => "能力を持たない",
-            CardFeature::Untouchable => "効果を受けない", // アークゲイン
-            CardFeature::TopCheck => "トップ確認",
-            CardFeature::BottomCheck => "ボトム確認",
-            CardFeature::Barrier => "バリア獲得",
-            CardFeature::MultiEner => "マルチエナ",
-            CardFeature::LrigTrash => "ルリグトラッシュ",
-            CardFeature::Charm => "チャーム",
-            CardFeature::Craft => "クラフト",
-            CardFeature::Acce => "アクセ",
-            CardFeature::Rise => "ライズ",
-            CardFeature::Recollect => "リコレクト",
-            CardFeature::SeekTop => "シーク",
-            CardFeature::EraseSkill => "能力消去",
-            CardFeature::CancelDamage => "ダメージ無効",
-            CardFeature::Reanimate => "トラッシュ場出し",
-            CardFeature::AdditionalAttack => "追加アタック",
-            CardFeature::UnGuardable => "ガード不可",
-            CardFeature::SalvageSpell => "スペル回収",
-            CardFeature::BanishOnAttack => "アタック時バニッシュ",
-            _ => "未処理フィーチャー"
-        };
-        write!(f, "{}", label)
+        write!(f, "{}", self.label())
     }
 }
 
-impl CardFeature {
-    pub fn to_bit(&self) -> (u64, u64) {
-        match self {
-            CardFeature::DoubleCrush => (1_u64, 0),
-            CardFeature::TripleCrush => (1 << 1, 0),
-            CardFeature::DiscardOpponent => (1 << 2, 0),
-            CardFeature::RandomDiscard => (1 << 3, 0),
-            CardFeature::Draw => (1 << 4, 0),
-            CardFeature::Assassin => (1 << 5, 0),
-            CardFeature::Freeze => (1 << 6, 0),
-            CardFeature::Drop => (1 << 7, 0),
-            // CardFeature::// DeckAttack => (1 << , 0),
-            CardFeature::OnDrop => (1 << 8, 0),
-            CardFeature::OnRefresh => (1 << 9, 0),
-            CardFeature::Lancer => (1 << 10, 0),
-            CardFeature::SLancer => (1 << 11, 0),
-            CardFeature::Penetrate => (1 << 12, 0),
-            CardFeature::NonAttackable => (1 << 13, 0),
-            CardFeature::Down => (1 << 14, 0),
-            CardFeature::Up => (1 << 15, 0),
-            CardFeature::Charge => (1 << 16, 0),
-            CardFeature::EnerAttack => (1 << 17, 0),
-            CardFeature::Trash => (1 << 18, 0),
-            CardFeature::Ener => (1 << 19, 0),
-            CardFeature::PowerUp => (1 << 20, 0),
-            CardFeature::PowerDown => (1 << 21, 0),
-            CardFeature::Bounce => (1 << 22, 0),
-            CardFeature::DeckBounce => (1 << 23, 0),
-            CardFeature::Salvage => (1 << 24, 0),
-            CardFeature::LifeBurst => (1 << 25, 0),
-            CardFeature::Shadow => (1 << 26, 0),
-            CardFeature::Invulnerable => (1 << 27, 0),
-            CardFeature::OnSpell => (1 << 28, 0),
-            CardFeature::OnArts => (1 << 29, 0),
-            CardFeature::OnPiece => (1 << 30, 0),
-            CardFeature::OnBanish => (1 << 31, 0),
-            CardFeature::Banish => (1 << 32, 0),
-            CardFeature::Guard => (1 << 33, 0),
-            CardFeature::OnGuard => (1 << 34, 0),
-            CardFeature::AttackNoEffect => (1 << 35, 0),
-            // CardFeature::OnAttack => (1 << 36, 0),
-            // CardFeature::OnAttackStart => (1 << 37, 0),
-            CardFeature::OnTouch => (1 << 38, 0),
-            CardFeature::Awake => (1 << 39, 0),
-            CardFeature::Exceed => (1 << 40, 0),
-            CardFeature::OnExceed => (1 << 41, 0),
-            CardFeature::AddLife => (1 << 42, 0),
-            CardFeature::OnBurst => (1 << 43, 0),
-            CardFeature::LifeTrash => (1 << 44, 0),
-            CardFeature::LifeCrush => (1 << 45, 0),
-            CardFeature::Damage => (1 << 46, 0),
-            CardFeature::OnLifeCrush => (1 << 47, 0),
-            CardFeature::Position => (1 << 48, 0),
-            CardFeature::Vanilla => (1 << 49, 0),
-            CardFeature::Untouchable => (1 << 50, 0),
-            // CardFeature::// アークゲイ => (1 << , 0),
-            CardFeature::TopCheck => (1 << 51, 0),
-            CardFeature::BottomCheck => (1 << 52, 0),
-            CardFeature::Barrier => (1 << 53, 0),
-            CardFeature::MultiEner => (1 << 54, 0),
-            CardFeature::LrigTrash => (1 << 55, 0),
-            CardFeature::Charm => (1 << 56, 0),
-            CardFeature::Craft => (1 << 57, 0),
-            CardFeature::Acce => (1 << 58, 0),
-            CardFeature::Rise => (1 << 59, 0),
-            CardFeature::Recollect => (1 << 60, 0),
-            CardFeature::SeekTop => (1 << 61, 0),
-            CardFeature::EraseSkill => (1 << 62, 0),
-            CardFeature::CancelDamage => (1 << 63, 0),
-
-
-            CardFeature::Reanimate => (0, 1 << 1),
-            CardFeature::AdditionalAttack => (0, 1 << 2),
-            CardFeature::UnGuardable => (0, 1 << 3),
-            CardFeature::SalvageSpell => (0, 1 << 4),
-            CardFeature::BanishOnAttack => (0, 1 << 5),
+// フィーチャーの定義。ラベル、カテゴリ、親フィーチャー、ビット位置、エクスポート用のインデックスはすべてここから生成される。
+// ビット位置は宣言順で自動的に決まるので、既存の位置を変えないよう新しいフィーチャーは末尾に追加すること。
+// 親フィーチャーを持つもの(Sランサーはランサーでもあるなど)は、親での検索にもヒットする
+macro_rules! def_features {
+    ($group:ident, [$(($name:ident, $desc:expr, $category:ident, [$($parent:ident),*])),* $(,)?]) => {
+        #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Serialize, Deserialize, JsonSchema)]
+        pub enum $group {
+            $(
+                $name,
+            )*
         }
+
+        impl Display for $group {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.label())
+            }
+        }
+
+        impl $group {
+            pub const ALL: &'static [$group] = &[
+                $(
+                    $group::$name,
+                )*
+            ];
+
+            pub fn label(&self) -> &'static str {
+                match self {
+                    $(
+                        $group::$name => $desc,
+                    )*
+                }
+            }
+
+            pub fn category(&self) -> FeatureCategory {
+                match self {
+                    $(
+                        $group::$name => FeatureCategory::$category,
+                    )*
+                }
+            }
+
+            pub fn parents(&self) -> &'static [$group] {
+                match self {
+                    $(
+                        $group::$name => &[$($group::$parent),*],
+                    )*
+                }
+            }
+
+            pub fn children(&self) -> Vec<$group> {
+                $group::ALL.iter().copied().filter(|f| f.parents().contains(self)).collect()
+            }
+
+            // 自身と、自身を含意するすべての子孫フィーチャー
+            pub fn with_descendants(&self) -> Vec<$group> {
+                let mut found: Vec<$group> = vec![*self];
+                let mut i = 0;
+                while i < found.len() {
+                    for child in found[i].children() {
+                        if !found.contains(&child) {
+                            found.push(child);
+                        }
+                    }
+                    i += 1;
+                }
+                found
+            }
+
+            // 自身と、自身が含意するすべての祖先フィーチャー
+            pub fn with_ancestors(&self) -> Vec<$group> {
+                let mut found: Vec<$group> = vec![*self];
+                let mut i = 0;
+                while i < found.len() {
+                    for parent in found[i].parents() {
+                        if !found.contains(parent) {
+                            found.push(*parent);
+                        }
+                    }
+                    i += 1;
+                }
+                found
+            }
+
+            pub fn bit_position(&self) -> u32 {
+                *self as u32
+            }
+
+            pub fn to_bit(&self) -> u128 {
+                1_u128 << self.bit_position()
+            }
+
+            // フロントエンド向けのビット表。ワード内のビット値はJSONの数値で正確に表せないので文字列で出力する
+            pub fn to_bit_index() -> serde_json::Value {
+                let mut map = serde_json::Map::new();
+                $(
+                    let position = $group::$name.bit_position();
+                    let mut inner_map = serde_json::Map::new();
+                    inner_map.insert("label".to_string(), serde_json::Value::String($desc.to_string()));
+                    inner_map.insert("position".to_string(), serde_json::Value::from(position));
+                    inner_map.insert("word".to_string(), serde_json::Value::from(position / 64));
+                    inner_map.insert("b".to_string(), serde_json::Value::String((1_u64 << (position % 64)).to_string()));
+                    inner_map.insert("category".to_string(), serde_json::Value::String(format!("{:?}", $group::$name.category())));
+                    inner_map.insert("deprecated".to_string(), serde_json::Value::Bool($group::$name.is_deprecated()));
+                    inner_map.insert("parents".to_string(), serde_json::Value::Array(
+                        $group::$name.parents().iter().map(|p| serde_json::Value::String(format!("{:?}", p))).collect()
+                    ));
+                    map.insert(stringify!($name).to_string(), serde_json::Value::Object(inner_map));
+                )*
+                serde_json::Value::Object(map)
+            }
+        }
+    }
+}
+
+def_features!(CardFeature, [
+    (DoubleCrush, "ダブルクラッシュ", Keyword, []),
+    (TripleCrush, "トリプルクラッシュ", Keyword, []),
+    (DiscardOpponent, "手札破壊", Removal, []),
+    (RandomDiscard, "ランダム手札破壊", Removal, [DiscardOpponent]),
+    (Draw, "ドロー", Resource, []),
+    (Assassin, "アサシン", Keyword, []),
+    (Freeze, "凍結", Control, []),
+    (Drop, "デッキドロップ", Resource, []), // DeckAttack,
+    (OnDrop, "デッキドロップ時", Trigger, []),
+    (OnRefresh, "リフレッシュ時", Trigger, []),
+    (Lancer, "ランサー", Keyword, []),
+    (SLancer, "Sランサー", Keyword, [Lancer]),
+    // UnGuardable に統合した。ビット位置を変えないため、また以前の書き出しを読むために残している
+    (Penetrate, "ガード不可(廃止)", Offense, []),
+    (NonAttackable, "アタック不可", Control, []),
+    (Down, "ダウン", Control, []),
+    (Up, "アップ", Resource, []),
+    (Charge, "エナチャージ", Resource, []),
+    (EnerAttack, "エナ破壊", Removal, []),
+    (Trash, "トラッシュ送り", Removal, []),
+    (Ener, "エナ送り", Removal, []),
+    (PowerUp, "パワーアップ", Offense, []),
+    (PowerDown, "パワーダウン", Removal, []),
+    (Bounce, "バウンス", Removal, []),
+    (DeckBounce, "デッキバウンス", Removal, []),
+    (Salvage, "回収", Resource, []),
+    (LifeBurst, "ライフバースト", Mechanic, []),
+    (Shadow, "シャドウ", Keyword, []),
+    (Invulnerable, "バニッシュされない", Protection, []),
+    (OnSpell, "スペル使用時", Trigger, []),
+    (OnArts, "アーツ使用時", Trigger, []),
+    (OnPiece, "ピース使用時", Trigger, []),
+    (OnBanish, "バニッシュした時", Trigger, []),
+    (Banish, "バニッシュ", Removal, []),
+    (Guard, "ガード", Protection, []),
+    (OnGuard, "ガードした時", Trigger, []),
+    (AttackNoEffect, "アタック無効", Protection, []),
+    // (OnAttack, "アタック時", Trigger, []),
+    // (OnAttackStart, "アタック開始時", Trigger, []),
+    (OnTouch, "対象になった時", Trigger, []),
+    (Awake, "覚醒", Mechanic, []),
+    (Exceed, "エクシード", Mechanic, []),
+    (OnExceed, "エクシードした時", Trigger, []),
+    (AddLife, "ライフクロス追加", Protection, []),
+    (OnBurst, "ライフバースト発動時", Trigger, []),
+    (LifeTrash, "ライフクロストラッシュ送り", Offense, []),
+    (LifeCrush, "クラッシュ", Offense, []),
+    (Damage, "ダメージ", Offense, []),
+    (OnLifeCrush, "クラッシュ時", Trigger, []),
+    (Position, "シグニゾーン移動", Mechanic, []),
+    (Vanilla, "能力を持たない", Mechanic, []),
+    (Untouchable, "効果を受けない", Protection, []), // アークゲイン
+    (TopCheck, "トップ確認", Resource, []),
+    (BottomCheck, "ボトム確認", Resource, []),
+    (Barrier, "バリア獲得", Protection, []),
+    (MultiEner, "マルチエナ", Keyword, []),
+    (LrigTrash, "ルリグトラッシュ", Mechanic, []),
+    (Charm, "チャーム", Mechanic, []),
+    (Craft, "クラフト", Mechanic, []),
+    (Acce, "アクセ", Mechanic, []),
+    (Rise, "ライズ", Mechanic, []),
+    (Recollect, "リコレクト", Mechanic, []),
+    (SeekTop, "シーク", Resource, []),
+    (EraseSkill, "能力消去", Control, []),
+    (CancelDamage, "ダメージ無効", Protection, []),
+    (Reanimate, "トラッシュ場出し", Resource, []),
+    (AdditionalAttack, "追加アタック", Offense, []),
+    (UnGuardable, "ガード不可", Offense, []),
+    (SalvageSpell, "スペル回収", Resource, [Salvage]),
+    (BanishOnAttack, "アタック時バニッシュ", Removal, [Banish]),
+]);
+
+impl CardFeature {
+    // もう検出しないフィーチャー。フィルタの選択肢には出さない
+    pub fn is_deprecated(&self) -> bool {
+        matches!(self, CardFeature::Penetrate)
     }
 }
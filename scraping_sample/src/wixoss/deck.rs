@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use crate::wixoss::card_no::{CardNo, CardNoError};
+
+// テキストのデッキリスト。1行に1種類で、枚数は省略すると1枚
+//     WXDi-P14-001 x2
+//     2 WXDi-P14-006
+//     WXDi-P12-071
+// 空行と # で始まる行は読み飛ばす。同じカードは枚数をまとめ、カード番号順に並べる
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeckList {
+    entries: BTreeMap<CardNo, u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeckListError {
+    InvalidCardNo { line: usize, error: CardNoError },
+    InvalidCount { line: usize, text: String },
+}
+
+impl Display for DeckListError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeckListError::InvalidCardNo { line, error } => write!(f, "line {}: {}", line, error),
+            DeckListError::InvalidCount { line, text } => write!(f, "line {}: invalid count: {}", line, text),
+        }
+    }
+}
+
+impl std::error::Error for DeckListError {}
+
+impl FromStr for DeckList {
+    type Err = DeckListError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut deck = Self::default();
+
+        for (i, line) in s.lines().enumerate() {
+            let line_no = i + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            let (card_no, count) = match tokens.as_slice() {
+                [card_no] => (*card_no, "1"),
+                [count, card_no] if count.chars().all(|c| c.is_ascii_digit()) => (*card_no, *count),
+                [card_no, count] => (*card_no, count.trim_start_matches(['x', '×'])),
+                _ => return Err(DeckListError::InvalidCount { line: line_no, text: line.into() }),
+            };
+
+            let card_no: CardNo = card_no.parse()
+                .map_err(|error| DeckListError::InvalidCardNo { line: line_no, error })?;
+            let count: u32 = count.parse().ok().filter(|c| *c > 0)
+                .ok_or_else(|| DeckListError::InvalidCount { line: line_no, text: line.into() })?;
+
+            deck.add(card_no, count);
+        }
+
+        Ok(deck)
+    }
+}
+
+impl DeckList {
+    pub fn add(&mut self, card_no: CardNo, count: u32) {
+        *self.entries.entry(card_no).or_default() += count;
+    }
+
+    pub fn count_of(&self, card_no: &CardNo) -> u32 {
+        self.entries.get(card_no).copied().unwrap_or(0)
+    }
+
+    // カード番号順
+    pub fn entries(&self) -> impl Iterator<Item = (&CardNo, u32)> {
+        self.entries.iter().map(|(card_no, count)| (card_no, *count))
+    }
+
+    pub fn total(&self) -> u32 {
+        self.entries.values().sum()
+    }
+}
+
+impl Display for DeckList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (card_no, count) in self.entries() {
+            writeln!(f, "{} x{}", card_no, count)?;
+        }
+        Ok(())
+    }
+}
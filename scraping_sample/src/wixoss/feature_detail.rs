@@ -0,0 +1,84 @@
+use std::collections::HashSet;
+use regex::Regex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use crate::wixoss::constants::CardFeature;
+use crate::wixoss::normalize::normalize_text;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum TargetSide {
+    Own,
+    Opponent,
+    Unspecified,
+}
+
+// 数量を伴うフィーチャー。amount は符号を除いた大きさ(パワー－5000なら5000)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct FeatureDetail {
+    pub feature: CardFeature,
+    pub amount: u32,
+    pub target: TargetSide,
+}
+
+impl FeatureDetail {
+    // 検出されたフィーチャーの分だけ残す。コストの「ライフクロス1枚をクラッシュする：」などは
+    // フィーチャーにならないので、max_amount と features が食い違わないようにする
+    pub fn from_skill_lines(lines: &[String], features: &HashSet<CardFeature>) -> Vec<Self> {
+        lines.iter()
+            .flat_map(|line| Self::from_line(line))
+            .filter(|detail| features.contains(&detail.feature))
+            .collect()
+    }
+
+    // パース済みのスキル行は正規化されているが、全角数字のままの文字列を渡されても読めるようにする
+    pub fn from_line(line: &str) -> Vec<Self> {
+        let line = normalize_text(line);
+
+        // (パターン, フィーチャー, 対象が書かれていない場合の既定値)
+        let patterns: Vec<(&str, CardFeature, TargetSide)> = vec![
+            (r"カードを(\d+)枚引", CardFeature::Draw, TargetSide::Own),
+            (r"パワーを[＋+](\d+)", CardFeature::PowerUp, TargetSide::Unspecified),
+            (r"パワーを[－−-](\d+)", CardFeature::PowerDown, TargetSide::Unspecified),
+            (r"デッキの上からカードを(\d+)枚トラッシュに置", CardFeature::Drop, TargetSide::Own),
+            (r"エナチャージ(\d+)", CardFeature::Charge, TargetSide::Own),
+            (r"ライフクロスを?(\d+)枚を?クラッシュ", CardFeature::LifeCrush, TargetSide::Opponent),
+        ];
+
+        let mut details: Vec<Self> = Vec::new();
+        for (pattern, feature, default_side) in patterns {
+            let re = Regex::new(pattern).unwrap();
+            for caps in re.captures_iter(&line) {
+                let whole = caps.get(0).unwrap();
+                if let Ok(amount) = caps[1].parse::<u32>() {
+                    details.push(Self {
+                        feature,
+                        amount,
+                        target: detect_side(&line[..whole.start()], &line[whole.end()..], default_side),
+                    });
+                }
+            }
+        }
+        details
+    }
+}
+
+// 効果の節(誘発条件「…とき、」やコスト「：」より後ろ)で最後に現れた「対戦相手」「あなた」「この」から
+// 対象の陣営を推定する。条件の側に書かれた「対戦相手」は対象ではない。
+// 能力のコスト(「：」の左側)に書かれたものは自分側として扱う
+fn detect_side(before: &str, after: &str, default_side: TargetSide) -> TargetSide {
+    let sentence = before.rsplit(['。', '：']).next().unwrap_or("");
+    let clause = sentence.rsplit("とき、").next().unwrap_or("");
+    let is_cost = after.split('。').next().unwrap_or("").contains('：');
+
+    let opponent = clause.rfind("対戦相手");
+    let own = ["あなた", "この"].iter().filter_map(|w| clause.rfind(w)).max();
+
+    match (opponent, own) {
+        (Some(o), Some(a)) if o > a => TargetSide::Opponent,
+        (Some(_), Some(_)) => TargetSide::Own,
+        (Some(_), None) => TargetSide::Opponent,
+        (None, Some(_)) => TargetSide::Own,
+        (None, None) if is_cost => TargetSide::Own,
+        (None, None) => default_side,
+    }
+}
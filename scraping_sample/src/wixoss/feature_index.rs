@@ -0,0 +1,100 @@
+use std::fmt::Write;
+use crate::wixoss::constants::{CardFeature, FeatureCategory};
+
+// フロントエンドのビットフィルタ用に CardFeature のビット表を書き出す。
+// FeatureSet のシリアライズ結果(decimal)とワード番号・ビット値の対応が一致するように、
+// どちらも CardFeature::bit_position() から計算している
+
+pub fn feature_index_json() -> String {
+    serde_json::to_string_pretty(&CardFeature::to_bit_index()).unwrap()
+}
+
+pub fn feature_index_typescript() -> String {
+    let mut out = String::new();
+
+    writeln!(out, "// このファイルは自動生成されている。編集せず `cargo run --example export_feature_index` で再生成すること").unwrap();
+    writeln!(out).unwrap();
+
+    let names: Vec<String> = CardFeature::ALL.iter().map(|f| format!("\"{:?}\"", f)).collect();
+    writeln!(out, "export type CardFeatureName =\n    | {};", names.join("\n    | ")).unwrap();
+    writeln!(out).unwrap();
+
+    let categories: Vec<String> = FeatureCategory::ALL.iter().map(|c| format!("\"{:?}\"", c)).collect();
+    writeln!(out, "export type FeatureCategory = {};", categories.join(" | ")).unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "export const FEATURE_CATEGORY_LABELS: Record<FeatureCategory, string> = {{").unwrap();
+    for category in FeatureCategory::ALL {
+        writeln!(out, "    {:?}: \"{}\",", category, category.label()).unwrap();
+    }
+    writeln!(out, "}};").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "export interface CardFeatureBit {{").unwrap();
+    writeln!(out, "    name: CardFeatureName;").unwrap();
+    writeln!(out, "    label: string;").unwrap();
+    writeln!(out, "    category: FeatureCategory;").unwrap();
+    writeln!(out, "    parents: CardFeatureName[];").unwrap();
+    writeln!(out, "    deprecated: boolean;").unwrap();
+    writeln!(out, "    position: number;").unwrap();
+    writeln!(out, "    word: number;").unwrap();
+    writeln!(out, "    bit: bigint;").unwrap();
+    writeln!(out, "}}").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "export const WORD_COUNT = 2;").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "export const CARD_FEATURES: Record<CardFeatureName, CardFeatureBit> = {{").unwrap();
+    for feature in CardFeature::ALL {
+        let position = feature.bit_position();
+        let parents: Vec<String> = feature.parents().iter().map(|p| format!("\"{:?}\"", p)).collect();
+        writeln!(
+            out,
+            "    {:?}: {{ name: \"{:?}\", label: \"{}\", category: \"{:?}\", parents: [{}], deprecated: {}, position: {}, word: {}, bit: {}n }},",
+            feature, feature, feature.label(), feature.category(), parents.join(", "), feature.is_deprecated(),
+            position, position / 64, 1_u64 << (position % 64)
+        ).unwrap();
+    }
+    writeln!(out, "}};").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "// FeatureSet の decimal 表現([下位ワード, 上位ワード])を読み込む").unwrap();
+    writeln!(out, "export const parseWords = (decimal: string[]): bigint[] => decimal.map((w) => BigInt(w));").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "export const toWords = (names: CardFeatureName[]): bigint[] => {{").unwrap();
+    writeln!(out, "    const words: bigint[] = new Array(WORD_COUNT).fill(0n);").unwrap();
+    writeln!(out, "    names.forEach((name) => {{").unwrap();
+    writeln!(out, "        const f = CARD_FEATURES[name];").unwrap();
+    writeln!(out, "        words[f.word] |= f.bit;").unwrap();
+    writeln!(out, "    }});").unwrap();
+    writeln!(out, "    return words;").unwrap();
+    writeln!(out, "}};").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "export const containsAll = (words: bigint[], condition: bigint[]): boolean =>").unwrap();
+    writeln!(out, "    condition.every((c, i) => (words[i] & c) === c);").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "export const containsAny = (words: bigint[], condition: bigint[]): boolean =>").unwrap();
+    writeln!(out, "    condition.some((c, i) => (words[i] & c) !== 0n);").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "export const descendantsOf = (name: CardFeatureName): CardFeatureName[] => {{").unwrap();
+    writeln!(out, "    const found: CardFeatureName[] = [name];").unwrap();
+    writeln!(out, "    for (let i = 0; i < found.length; i++) {{").unwrap();
+    writeln!(out, "        (Object.keys(CARD_FEATURES) as CardFeatureName[])").unwrap();
+    writeln!(out, "            .filter((n) => CARD_FEATURES[n].parents.includes(found[i]) && !found.includes(n))").unwrap();
+    writeln!(out, "            .forEach((n) => found.push(n));").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "    return found;").unwrap();
+    writeln!(out, "}};").unwrap();
+    writeln!(out).unwrap();
+
+    writeln!(out, "// 親フィーチャーでの検索では子フィーチャーを持つカードもヒットさせる").unwrap();
+    writeln!(out, "export const hasFeature = (words: bigint[], name: CardFeatureName): boolean =>").unwrap();
+    writeln!(out, "    containsAny(words, toWords(descendantsOf(name)));").unwrap();
+
+    out
+}
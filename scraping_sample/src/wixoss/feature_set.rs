@@ -0,0 +1,120 @@
+use std::collections::HashSet;
+use std::fmt::{Binary, Formatter};
+use serde::{Serialize, Serializer};
+use serde::ser::SerializeStruct;
+use crate::wixoss::constants::CardFeature;
+
+// CardFeatureのビット集合。CardFeature::bit_position()の位置にビットを立てる
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct FeatureSet {
+    bits: u128,
+}
+
+impl FeatureSet {
+    pub fn new() -> Self {
+        Self { bits: 0 }
+    }
+
+    pub fn from_bits(bits: u128) -> Self {
+        Self { bits }
+    }
+
+    // 下位ワード、上位ワードの順
+    pub fn from_words(words: [u64; 2]) -> Self {
+        Self { bits: (words[0] as u128) | ((words[1] as u128) << 64) }
+    }
+
+    pub fn bits(&self) -> u128 {
+        self.bits
+    }
+
+    pub fn to_words(&self) -> [u64; 2] {
+        [self.bits as u64, (self.bits >> 64) as u64]
+    }
+
+    pub fn to_hex_pair(&self) -> (String, String) {
+        let [low, high] = self.to_words();
+        (format!("{:#018x}", low), format!("{:#018x}", high))
+    }
+
+    // JSONの数値はu64を正確に表現できないので、10進数は文字列で返す
+    pub fn to_decimal_pair(&self) -> (String, String) {
+        let [low, high] = self.to_words();
+        (low.to_string(), high.to_string())
+    }
+
+    pub fn insert(&mut self, feature: CardFeature) {
+        self.bits |= feature.to_bit();
+    }
+
+    pub fn remove(&mut self, feature: CardFeature) {
+        self.bits &= !feature.to_bit();
+    }
+
+    pub fn contains(&self, feature: &CardFeature) -> bool {
+        self.bits & feature.to_bit() != 0
+    }
+
+    pub fn contains_all(&self, other: &FeatureSet) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    pub fn contains_any(&self, other: &FeatureSet) -> bool {
+        self.bits & other.bits != 0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    // 含意される親フィーチャーを補った集合。親フィーチャーでの検索に子フィーチャーを持つカードもヒットさせる
+    pub fn with_implied(&self) -> Self {
+        self.iter().flat_map(|f| f.with_ancestors()).collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=CardFeature> + '_ {
+        CardFeature::ALL.iter().copied().filter(|f| self.contains(f))
+    }
+}
+
+impl FromIterator<CardFeature> for FeatureSet {
+    fn from_iter<T: IntoIterator<Item=CardFeature>>(iter: T) -> Self {
+        let mut set = Self::new();
+        iter.into_iter().for_each(|f| set.insert(f));
+        set
+    }
+}
+
+impl<'a> FromIterator<&'a CardFeature> for FeatureSet {
+    fn from_iter<T: IntoIterator<Item=&'a CardFeature>>(iter: T) -> Self {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl From<&HashSet<CardFeature>> for FeatureSet {
+    fn from(value: &HashSet<CardFeature>) -> Self {
+        value.iter().collect()
+    }
+}
+
+impl Binary for FeatureSet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Binary::fmt(&self.bits, f)
+    }
+}
+
+impl Serialize for FeatureSet {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        let (hex_low, hex_high) = self.to_hex_pair();
+        let (dec_low, dec_high) = self.to_decimal_pair();
+
+        let mut state = serializer.serialize_struct("FeatureSet", 2)?;
+        state.serialize_field("hex", &[hex_low, hex_high])?;
+        state.serialize_field("decimal", &[dec_low, dec_high])?;
+        state.end()
+    }
+}
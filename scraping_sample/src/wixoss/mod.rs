@@ -1,16 +1,35 @@
 #[macro_use]
 pub mod constants;
-
-use std::collections::HashSet;
+pub mod feature_set;
+pub mod feature_index;
+pub mod feature_detail;
+pub mod skill_text;
+pub mod normalize;
+pub mod reading;
+pub mod card_no;
+pub mod deck;
+pub mod schema;
+
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::{Display, Formatter};
+use std::path::Path;
 use scraper::{Html, Selector};
 use regex::Regex;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use schemars::JsonSchema;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
 use crate::features;
 use crate::wixoss::constants::CardFeature;
+use crate::wixoss::feature_set::FeatureSet;
+use crate::wixoss::feature_detail::FeatureDetail;
+use crate::wixoss::skill_text::{RenderMode, SkillRenderer};
+use crate::wixoss::normalize::normalize_text;
+use crate::wixoss::reading::ReadingKeys;
+use crate::wixoss::card_no::{CardNo, CardNoError};
 use serde::ser::SerializeSeq;
 
-#[derive(Debug, Clone, PartialEq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema)]
 pub enum CardType {
     Lrig,
     LrigAssist,
@@ -52,7 +71,40 @@ impl Display for CardType {
 }
 
 pub trait WixossCard: Sized {
-    fn from_source(source: String) -> Self;
+    fn from_page(page: &CardPage) -> Self;
+
+    fn from_source(source: String) -> Result<Self, CardParseError> {
+        CardPage::parse(&source).map(|page| Self::from_page(&page))
+    }
+}
+
+// カード詳細ページとしてパースできなかった理由
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CardParseError {
+    UnknownCardType,
+    MissingCardNo,
+    InvalidCardNo(CardNoError),
+    // カードデータの欄(.cardData の dd)が足りない
+    MissingCells(usize),
+}
+
+impl Display for CardParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CardParseError::UnknownCardType => write!(f, "card type not detected"),
+            CardParseError::MissingCardNo => write!(f, "card number not found"),
+            CardParseError::InvalidCardNo(e) => write!(f, "{}", e),
+            CardParseError::MissingCells(found) => write!(f, "expected {} card data cells, found {}", CARD_DATA_CELLS, found),
+        }
+    }
+}
+
+impl std::error::Error for CardParseError {}
+
+impl From<CardNoError> for CardParseError {
+    fn from(e: CardNoError) -> Self {
+        CardParseError::InvalidCardNo(e)
+    }
 }
 
 // impl Display for dyn WixossCard {
@@ -64,7 +116,7 @@ pub trait WixossCard: Sized {
 //     }
 // }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 enum Format {
     AllStar,
     KeySelection,
@@ -81,6 +133,84 @@ impl Display for Format {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum StoryTag {
+    Dissona,
+    // 未対応のストーリーアイコン(画像ファイル名から icon_txt_ と拡張子を除いたもの)
+    Icon(String),
+    Text(String),
+}
+
+impl Display for StoryTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StoryTag::Dissona => write!(f, "ディソナ"),
+            StoryTag::Icon(name) => write!(f, "{}", name),
+            StoryTag::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+// カードデータの「ストーリー」欄。アイコンと文字列が並ぶことがある
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Story {
+    tags: Vec<StoryTag>,
+}
+
+impl Story {
+    pub fn tags(&self) -> &[StoryTag] {
+        &self.tags
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    pub fn is_dissona(&self) -> bool {
+        self.tags.contains(&StoryTag::Dissona)
+    }
+}
+
+// 以前の書き出し形式("ディソナ" または "")も読めるようにする
+impl<'de> Deserialize<'de> for Story {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum StoryRepr {
+            Tags(Vec<StoryTag>),
+            Legacy(String),
+        }
+
+        let tags = match StoryRepr::deserialize(deserializer)? {
+            StoryRepr::Tags(tags) => tags,
+            StoryRepr::Legacy(text) => match text.as_str() {
+                "" => Vec::new(),
+                "ディソナ" => vec![StoryTag::Dissona],
+                _ => vec![StoryTag::Text(text)],
+            },
+        };
+        Ok(Self { tags })
+    }
+}
+
+// スキーマには現在の書き出し形式(タグの配列)だけを載せる
+impl JsonSchema for Story {
+    fn schema_name() -> String {
+        "Story".into()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        gen.subschema_for::<Vec<StoryTag>>()
+    }
+}
+
+impl Display for Story {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.tags.iter().map(|t| t.to_string()).collect::<Vec<String>>().join(", "))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct OptionString {
     value: Option<String>,
@@ -115,6 +245,25 @@ impl Serialize for OptionString {
     }
 }
 
+// シリアライズ時に空文字・"-"・Noneを区別しないので、空文字はNoneとして読む
+impl<'de> Deserialize<'de> for OptionString {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        let value = Option::<String>::deserialize(deserializer)?.unwrap_or_default();
+        Ok(Self::from_string(value))
+    }
+}
+
+// 値が無い場合も空文字で書き出す
+impl JsonSchema for OptionString {
+    fn schema_name() -> String {
+        "OptionString".into()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
 impl Display for OptionString {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match &self.value {
@@ -171,35 +320,272 @@ impl Serialize for Skills {
     }
 }
 
+impl<'de> Deserialize<'de> for Skills {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        Vec::<String>::deserialize(deserializer).map(Self::from_vec)
+    }
+}
+
+impl JsonSchema for Skills {
+    fn schema_name() -> String {
+        "Skills".into()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        Vec::<String>::json_schema(gen)
+    }
+}
+
 impl Display for Skills {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.value.join("\n"))
     }
 }
 
+// 一つの能力と、そこから検出されたフィーチャー・数量
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Ability {
+    pub text: String,
+
+    #[serde(serialize_with = "sorted_features_serialize")]
+    pub features: HashSet<CardFeature>,
+    #[serde(default)]
+    pub details: Vec<FeatureDetail>,
+}
+
+impl Ability {
+    fn new(text: String, features: Vec<CardFeature>) -> Self {
+        let features: HashSet<CardFeature> = features.into_iter().collect();
+        let details = FeatureDetail::from_skill_lines(std::slice::from_ref(&text), &features);
+        Self { text, features, details }
+    }
+}
+
+impl Display for Ability {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+// 正規化する前の表記。表示用に、正規化で変わったカードにだけ持たせる。
+// skill と life_burst はページに書かれたままのテキスト(注釈を除く前のもの。ライフバーストの「ライフバースト：」は除く)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct OriginalText {
+    pub name: String,
+    pub pronounce: String,
+    pub skill: Vec<String>,
+    pub life_burst: Option<String>,
+    // 名前・読み・テキスト以外の欄(.cardData の各欄、レアリティ、イラスト)のうち、正規化で変わったもの。
+    // キーは欄の見出しで、値はページのHTMLのまま
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub cells: BTreeMap<String, String>,
+}
+
+impl OriginalText {
+    fn from_lines(name: String, pronounce: String, lines: &[SkillLine], cells: BTreeMap<String, String>) -> Self {
+        let mut skill: Vec<String> = Vec::new();
+        let mut life_burst_lines: Vec<String> = Vec::new();
+        for line in lines.iter().filter(|line| !line.text.is_empty()) {
+            let text = replace_img_with_alt(line.source.clone()).trim().to_string();
+            match line.kind {
+                SkillLineKind::Own => skill.push(text),
+                SkillLineKind::LifeBurst => life_burst_lines.push(text.trim_start_matches("ライフバースト：").to_string()),
+                SkillLineKind::Granted => {}
+            }
+        }
+        let life_burst = (!life_burst_lines.is_empty()).then(|| life_burst_lines.join("\n"));
+
+        Self { name, pronounce, skill, life_burst, cells }
+    }
+}
+
+// カード画像。url は詳細ページの .cardImg img のsrc。
+// local_path は画像キャッシュ(crate::images)に取得済みの場合だけ入る。キャッシュの root からの相対パス
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CardImage {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local_path: Option<String>,
+}
+
+// カード詳細ページの .cardData の欄の数(カード種類からストーリーまで)。種類によって使わない欄も「-」で並んでいる
+const CARD_DATA_CELLS: usize = 12;
+
+// カード詳細ページを一度だけパースして、各欄を正規化した値で持つ。
+// 正規化で表記が変わった欄があれば、元の表記を original に残す
+pub struct CardPage {
+    card_type: CardType,
+    no: CardNo,
+    name: String,
+    pronounce: String,
+    artist: String,
+    rarity: String,
+    data: Vec<String>,
+    skill: Skills,
+    features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
+    original: Option<OriginalText>,
+    image: Option<CardImage>,
+}
+
+impl CardPage {
+    pub fn parse(source: &str) -> Result<Self, CardParseError> {
+        let document: Html = Html::parse_document(source);
+        let select_inner_html = |selector: &str| -> Option<String> {
+            let selector = Selector::parse(selector).unwrap();
+            document.select(&selector).next().map(|element| element.inner_html())
+        };
+
+        // 見出し(dt)と値(dd)は同じ順に並んでいる。.cardData の中にはFAQの dl もあるので、直下の dl だけを見る
+        let selector_card_label = Selector::parse(".cardData > dl > dt").unwrap();
+        let labels: Vec<String> = document.select(&selector_card_label)
+            .map(|element| element.text().collect::<String>().trim().to_string())
+            .collect();
+        let selector_card_data = Selector::parse(".cardData > dl > dd").unwrap();
+        let raw_data: Vec<String> = document.select(&selector_card_data)
+            .map(|element| element.inner_html())
+            .collect();
+        let data: Vec<String> = raw_data.iter().map(|cell| normalize_text(cell)).collect();
+
+        let card_type = data.first()
+            .map(|cell| card_type_from_cell(cell))
+            .unwrap_or(CardType::Unknown);
+        if card_type == CardType::Unknown {
+            return Err(CardParseError::UnknownCardType);
+        }
+        if data.len() < CARD_DATA_CELLS {
+            return Err(CardParseError::MissingCells(data.len()));
+        }
+
+        let no: CardNo = match select_inner_html(".cardNum") {
+            Some(card_no) => normalize_text(&card_no).parse()?,
+            None => return Err(CardParseError::MissingCardNo),
+        };
+
+        let (raw_name, raw_pronounce) = match select_inner_html(".cardName") {
+            Some(card_name) => element_to_name_and_pronounce(card_name),
+            None => ("unknown".into(), "unknown".into())
+        };
+        let raw_rarity = select_inner_html(".cardRarity").unwrap_or_else(|| "unknown rarity".into());
+        let raw_artist = select_inner_html(".cardImg p span").unwrap_or_else(|| "unknown artist".into());
+
+        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
+        let card_skills: Vec<String> = document.select(&selector_card_skill)
+            .map(|element| element.inner_html())
+            .collect();
+
+        let selector_image = Selector::parse(".cardImg img").unwrap();
+        let image = document.select(&selector_image).next()
+            .and_then(|img| img.value().attr("src"))
+            .filter(|src| !src.is_empty())
+            .map(|src| CardImage { url: src.to_string(), local_path: None });
+
+        let name = normalize_text(&raw_name);
+        let pronounce = normalize_text(&raw_pronounce);
+        let rarity = normalize_text(&raw_rarity);
+        let artist = normalize_text(&raw_artist);
+
+        let lines = parse_skill_lines(card_skills);
+        let (skill, features, granted_skills, life_burst) = parse_card_skill(&lines);
+
+        let mut cells: BTreeMap<String, String> = BTreeMap::new();
+        for (i, (raw, normalized)) in raw_data.iter().zip(&data).enumerate() {
+            if raw != normalized {
+                let label = labels.get(i).cloned().unwrap_or_else(|| i.to_string());
+                cells.insert(label, raw.clone());
+            }
+        }
+        for (label, raw, normalized) in [("レアリティ", &raw_rarity, &rarity), ("イラスト", &raw_artist, &artist)] {
+            if raw != normalized {
+                cells.insert(label.to_string(), raw.clone());
+            }
+        }
+
+        let changed = raw_name != name || raw_pronounce != pronounce || !cells.is_empty()
+            || lines.iter().any(|line| line.normalized);
+        let original = changed
+            .then(|| OriginalText::from_lines(raw_name, raw_pronounce, &lines, cells));
+
+        Ok(Self {
+            card_type,
+            no,
+            name,
+            pronounce,
+            artist,
+            rarity,
+            data,
+            skill,
+            features,
+            granted_skills,
+            life_burst,
+            original,
+            image,
+        })
+    }
+}
+
 fn custom_vec_string_serialize<S>(value: &[String], serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
 {
-let joined = value.iter()
-    .filter_map(|t| if t != "-" { Some(t.as_str()) } else { None })
-    .collect::<Vec<&str>>()
-    .join(", ");
+    // "-"は未設定を表すので書き出さない
+    let values: Vec<&String> = value.iter().filter(|t| *t != "-").collect();
 
-    serializer.serialize_str(&joined)
+    let mut seq = serializer.serialize_seq(Some(values.len()))?;
+    for e in values {
+        seq.serialize_element(e)?;
+    }
+    seq.end()
+}
 
-    // let mut seq = serializer.serialize_seq(Some(value.len()))?;
-    // for e in value.iter() {
-    //     seq.serialize_element(e)?;
-    // }
-    // seq.end()
+fn custom_vec_string_deserialize<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+    where
+        D: Deserializer<'de>,
+{
+    // 以前は ", " で連結した文字列として書き出していたので、それも読めるようにする
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum TimeRepr {
+        List(Vec<String>),
+        Joined(String),
+    }
+
+    Ok(match TimeRepr::deserialize(deserializer)? {
+        TimeRepr::List(values) => values,
+        TimeRepr::Joined(joined) => joined.split(", ")
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_string())
+            .collect(),
+    })
+}
+
+fn sorted_features_serialize<S>(value: &HashSet<CardFeature>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+{
+    // HashSetの順序は実行ごとに変わるので、スナップショット比較できるよう定義順に並べる
+    let mut sorted: Vec<&CardFeature> = value.iter().collect();
+    sorted.sort();
+
+    let mut seq = serializer.serialize_seq(Some(sorted.len()))?;
+    for e in sorted {
+        seq.serialize_element(e)?;
+    }
+    seq.end()
 }
 
-#[derive(Debug, Serialize)]
+// 書き出したJSONから読み戻せる。後から追加したフィールドは、古い書き出しに無くても既定値で読む。
+// 他のフィールドから決まる reading と is_dissona は、読み込んだ後に作り直す
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(remote = "Self")]
 pub struct Card {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
+    // 読みから作った検索・並べ替え用のキー
+    #[serde(default)]
+    reading: ReadingKeys,
     artist: String,
     pub card_type: CardType,
     klass: OptionString,
@@ -210,19 +596,54 @@ pub struct Card {
     power: OptionString,
     user: OptionString,
 
-    #[serde(serialize_with = "custom_vec_string_serialize")]
+    #[serde(serialize_with = "custom_vec_string_serialize", deserialize_with = "custom_vec_string_deserialize")]
     time: Vec<String>,
 
-    pub story: OptionString,
+    pub story: Story,
+    #[serde(default)]
+    is_dissona: bool,
     format: Format,
     rarity: String,
     skill: Skills,
+
+    #[serde(serialize_with = "sorted_features_serialize")]
     features: HashSet<CardFeature>,
+    // このカード自身のスキルの数量。ライフバーストと他のカードに与える能力の数量は、それぞれの details にある
+    #[serde(default)]
+    feature_details: Vec<FeatureDetail>,
+    // 他のカードに与える能力(「以下の能力を得る」の枠内)
+    #[serde(default)]
+    granted_skills: Vec<Ability>,
+    // ライフバースト。複数行の場合は改行でつなぐ。フィーチャーは効果部分から検出したもので、
+    // features には含めない(features にはライフバーストを持つことを表す LifeBurst だけが入る)
+    #[serde(default)]
+    life_burst: Option<Ability>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    original: Option<OriginalText>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    image: Option<CardImage>,
+}
+
+impl Serialize for Card {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        Card::serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        let mut card = Card::deserialize(deserializer)?;
+        card.reading = ReadingKeys::from_pronounce(&card.pronounce);
+        card.is_dissona = card.story.is_dissona();
+        Ok(card)
+    }
 }
 
 impl Display for Card {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {}\n{}\n{}",
+        write!(f, "{} {} {} {} {} {} {} {} {} {} {} {} {} {} {} {}\n{}\n{}{}",
                self.no,
                self.name,
                self.pronounce,
@@ -240,11 +661,56 @@ impl Display for Card {
                self.format,
                self.rarity,
                self.skill,
+               self.life_burst.as_ref().map(|lb| format!("LB:{}\n", lb)).unwrap_or_default(),
                self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", ")
         )
     }
 }
 
+// 種類ごとの構造体のどれか。カードの種類による振り分けはここだけで行う
+macro_rules! def_typed_cards {
+    ($($kind:ident),* $(,)?) => {
+        enum TypedCard {
+            $(
+                $kind($kind),
+            )*
+        }
+
+        impl TypedCard {
+            fn from_page(page: &CardPage) -> Result<Self, CardParseError> {
+                match page.card_type {
+                    $(
+                        CardType::$kind => Ok(TypedCard::$kind($kind::from_page(page))),
+                    )*
+                    _ => Err(CardParseError::UnknownCardType),
+                }
+            }
+        }
+
+        impl From<TypedCard> for Card {
+            fn from(val: TypedCard) -> Self {
+                match val {
+                    $(
+                        TypedCard::$kind(card) => card.into(),
+                    )*
+                }
+            }
+        }
+
+        impl Display for TypedCard {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $(
+                        TypedCard::$kind(card) => card.fmt(f),
+                    )*
+                }
+            }
+        }
+    };
+}
+
+def_typed_cards!(Lrig, LrigAssist, Arts, Key, Signi, Spell, Resona, ArtsCraft, ResonaCraft, SpellCraft, Piece, PieceRelay, Token);
+
 impl Card {
     pub fn detect_card_type(text: &str) -> CardType {
         let document: Html = Html::parse_document(text);
@@ -255,50 +721,125 @@ impl Card {
             card_data.push(element.inner_html());
         }
 
-        let text = card_data[0].clone();
+        card_data.first()
+            .map(|cell| card_type_from_cell(cell))
+            .unwrap_or(CardType::Unknown)
+    }
+
+    // 各フィールドは全角英数字・半角カナを正規化した表記になる。
+    // 正規化で表記が変わった場合は、元の表記を original に残す
+    pub fn card_from_html(text: &str) -> Result<Self, CardParseError> {
+        let page = CardPage::parse(text)?;
+        let mut card: Card = TypedCard::from_page(&page)?.into();
 
-        #[allow(unreachable_patterns)]
-        match text.as_str() {
-            "ルリグ" => CardType::Lrig,
-            "アシストルリグ" => CardType::LrigAssist,
-            "アーツ" => CardType::Arts,
-            "キー" => CardType::Key,
-            "シグニ" => CardType::Signi,
-            "スペル" => CardType::Spell,
-            "レゾナ" => CardType::Resona,
-            "アーツ<br />\nクラフト" => CardType::ArtsCraft,
-            "シグニ<br />\nクラフト" => CardType::ResonaCraft,
-            "スペル<br />\nクラフト" => CardType::SpellCraft,
-            "ピース" => CardType::Piece,
-            "ピース<br />\nリレー" => CardType::PieceRelay,
-            "コイン" => CardType::Token,
-            "トークン" => CardType::Token,
-            _ => CardType::Unknown
-        }
-    }
-
-    pub fn card_from_html(text: &str) -> Option<Self> {
-        match Self::detect_card_type(text.clone()) {
-            CardType::Lrig => Some(Lrig::from_source(text.to_owned()).into()),
-            CardType::LrigAssist => Some(LrigAssist::from_source(text.to_owned()).into()),
-            CardType::Arts => Some(Arts::from_source(text.to_owned()).into()),
-            CardType::Key => Some(Key::from_source(text.to_owned()).into()),
-            CardType::Signi => Some(Signi::from_source(text.to_owned()).into()),
-            CardType::Spell => Some(Spell::from_source(text.to_owned()).into()),
-            CardType::Resona => Some(Resona::from_source(text.to_owned()).into()),
-            CardType::ArtsCraft => Some(ArtsCraft::from_source(text.to_owned()).into()),
-            CardType::ResonaCraft => Some(ResonaCraft::from_source(text.to_owned()).into()),
-            CardType::SpellCraft => Some(SpellCraft::from_source(text.to_owned()).into()),
-            CardType::Piece => Some(Piece::from_source(text.to_owned()).into()),
-            CardType::PieceRelay => Some(PieceRelay::from_source(text.to_owned()).into()),
-            CardType::Token => Some(Token::from_source(text.to_owned()).into()),
-            _ => None
+        card.original = page.original.clone();
+        card.image = page.image.clone();
+
+        Ok(card)
+    }
+
+    // 種類ごとの構造体のDisplayで表示する
+    pub fn describe_html(text: &str) -> Result<String, CardParseError> {
+        let page = CardPage::parse(text)?;
+        Ok(TypedCard::from_page(&page)?.to_string())
+    }
+
+    pub fn no(&self) -> &str {
+        self.no.as_str()
+    }
+
+    pub fn card_no(&self) -> &CardNo {
+        &self.no
+    }
+
+    pub fn image(&self) -> Option<&CardImage> {
+        self.image.as_ref()
+    }
+
+    // 画像キャッシュに取得した場所を、キャッシュの root からの相対パス(objects/…)で記録する。
+    // 画像のURLが無いカードでは何もしない
+    pub fn set_image_path(&mut self, path: &Path) {
+        if let Some(image) = self.image.as_mut() {
+            image.local_path = Some(path.to_string_lossy().into_owned());
         }
     }
 
+    pub fn features(&self) -> &HashSet<CardFeature> {
+        &self.features
+    }
+
+    pub fn feature_set(&self) -> FeatureSet {
+        FeatureSet::from(&self.features)
+    }
+
+    pub fn is_dissona(&self) -> bool {
+        self.is_dissona
+    }
+
+    pub fn reading(&self) -> &ReadingKeys {
+        &self.reading
+    }
+
+    // 読みのひらがな・カタカナ・ローマ字での前方一致(入力途中の検索用)
+    pub fn matches_reading(&self, query: &str) -> bool {
+        self.reading.starts_with(query)
+    }
+
+    pub fn original(&self) -> Option<&OriginalText> {
+        self.original.as_ref()
+    }
+
+    pub fn granted_skills(&self) -> &[Ability] {
+        &self.granted_skills
+    }
+
+    pub fn life_burst(&self) -> Option<&Ability> {
+        self.life_burst.as_ref()
+    }
+
+    pub fn has_life_burst(&self) -> bool {
+        self.life_burst.is_some()
+    }
+
+    pub fn feature_details(&self) -> &[FeatureDetail] {
+        &self.feature_details
+    }
+
+    // 例: パワー－8000以上 → max_amount(CardFeature::PowerDown) >= Some(8000)
+    pub fn max_amount(&self, feature: CardFeature) -> Option<u32> {
+        self.feature_details.iter()
+            .filter(|d| d.feature == feature)
+            .map(|d| d.amount)
+            .max()
+    }
+
     pub fn check_have_feature(self: &Self, card_feature: CardFeature) -> bool {
         println!("feature check: positive {}", card_feature);
-        self.features.contains(&card_feature)
+        self.feature_set().with_implied().contains(&card_feature)
+    }
+}
+
+fn card_type_from_cell(cell: &str) -> CardType {
+    // inner_html()は<br />を<br>として返すので改行ごと取り除いて比較する
+    let text = flatten_break(cell.to_string());
+
+    #[allow(unreachable_patterns)]
+    match text.as_str() {
+        "ルリグ" => CardType::Lrig,
+        "アシストルリグ" => CardType::LrigAssist,
+        "アーツ" => CardType::Arts,
+        "キー" => CardType::Key,
+        "シグニ" => CardType::Signi,
+        "スペル" => CardType::Spell,
+        "レゾナ" => CardType::Resona,
+        "アーツクラフト" => CardType::ArtsCraft,
+        "シグニクラフト" => CardType::ResonaCraft,
+        "スペルクラフト" => CardType::SpellCraft,
+        "ピース" => CardType::Piece,
+        "ピースリレー" => CardType::PieceRelay,
+        "コイン" => CardType::Token,
+        "トークン" => CardType::Token,
+        _ => CardType::Unknown
     }
 }
 
@@ -331,7 +872,7 @@ fn element_to_name_and_pronounce(source: String) -> (String, String) {
 
 #[derive(Debug)]
 pub struct Piece {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
     artist: String,
@@ -343,11 +884,13 @@ pub struct Piece {
     // power: Option<String>,
     user: OptionString,
     time: Vec<String>,
-    story: OptionString,
+    story: Story,
     format: Format,
     rarity: String,
     skill: Skills,
     features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
 }
 
 impl From<Piece> for Card {
@@ -356,6 +899,7 @@ impl From<Piece> for Card {
             no: val.no.clone(),
             name: val.name.clone(),
             pronounce: val.pronounce.clone(),
+            reading: ReadingKeys::from_pronounce(&val.pronounce),
             artist: val.artist.clone(),
             card_type: val.card_type.clone(),
             klass: OptionString::empty(),
@@ -367,62 +911,29 @@ impl From<Piece> for Card {
             user: val.user.clone(),
             time: val.time.clone(),
             story: val.story.clone(),
+            is_dissona: val.story.is_dissona(),
             format: val.format.clone(),
             rarity: val.rarity.clone(),
             skill: val.skill.clone(),
             features: val.features.clone(),
+            feature_details: FeatureDetail::from_skill_lines(&val.skill.value, &val.features),
+            granted_skills: val.granted_skills.clone(),
+            life_burst: val.life_burst.clone(),
+            original: None,
+            image: None,
         }
     }
 }
 
 impl WixossCard for Piece {
-    fn from_source(source: String) -> Self {
-        let document: Html = Html::parse_document(&source);
-
-        let selector_card_num = Selector::parse(".cardNum").unwrap();
-        let card_no = match document.select(&selector_card_num).next() {
-            Some(card_no) => card_no.inner_html(),
-            None => "unknown".into()
-        };
-
-        let selector_card_name = Selector::parse(".cardName").unwrap();
-        let card_name = match document.select(&selector_card_name).next() {
-            Some(card_name) => element_to_name_and_pronounce(card_name.inner_html()),
-            None => ("unknown".into(), "unknown".into())
-        };
-
-        let selector_rarity = Selector::parse(".cardRarity").unwrap();
-        let card_rarity = match document.select(&selector_rarity).next() {
-            Some(card_rarity) => card_rarity.inner_html(),
-            None => "unknown rarity".into()
-        };
-
-        let selector_artist = Selector::parse(".cardImg p span").unwrap();
-        let artist = match document.select(&selector_artist).next() {
-            Some(artist) => artist.inner_html(),
-            None => "unknown artist".into()
-        };
-
-        let selector_card_data = Selector::parse(".cardData dd").unwrap();
-
-        let mut card_data: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_data) {
-            card_data.push(element.inner_html());
-        }
-
-        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
-        let mut card_skills: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_skill) {
-            card_skills.push(element.inner_html());
-        }
-
-        let (skill, features) = parse_card_skill(card_skills.clone());
+    fn from_page(page: &CardPage) -> Self {
+        let card_data = &page.data;
 
         Self {
-            no: card_no,
-            name: card_name.0,
-            pronounce: card_name.1,
-            artist,
+            no: page.no.clone(),
+            name: page.name.clone(),
+            pronounce: page.pronounce.clone(),
+            artist: page.artist.clone(),
             card_type: CardType::Piece,
             color: card_data[2].clone(),
             cost: OptionString::from_string(flatten_break(card_data[5].clone())),
@@ -430,9 +941,11 @@ impl WixossCard for Piece {
             time: split_by_break(card_data[9].clone()),
             story: parse_story(card_data[11].clone().trim().to_string()),
             format: parse_format(card_data[10].clone()),
-            rarity: card_rarity,
-            skill,
-            features,
+            rarity: page.rarity.clone(),
+            skill: page.skill.clone(),
+            features: page.features.clone(),
+            granted_skills: page.granted_skills.clone(),
+            life_burst: page.life_burst.clone(),
         }
     }
 }
@@ -455,6 +968,9 @@ impl Display for Piece {
         writeln!(f, "フォーマット\t:{}", self.format)?;
         writeln!(f, "レアリティ\t:{}", self.rarity)?;
         writeln!(f, "テキスト({})\t:{}", self.skill.value.len(), self.skill)?;
+        if let Some(life_burst) = &self.life_burst {
+            writeln!(f, "ライフバースト\t:{}", life_burst)?;
+        }
         writeln!(f, "フィーチャー({})\t:{:?}", self.features.len(), self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "))?;
         write!(f, "")
     }
@@ -462,7 +978,7 @@ impl Display for Piece {
 
 #[derive(Debug)]
 pub struct PieceRelay {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
     artist: String,
@@ -474,11 +990,13 @@ pub struct PieceRelay {
     // power: Option<String>,
     user: OptionString,
     time: Vec<String>,
-    story: OptionString,
+    story: Story,
     format: Format,
     rarity: String,
     skill: Skills,
     features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
 }
 
 impl From<PieceRelay> for Card {
@@ -487,6 +1005,7 @@ impl From<PieceRelay> for Card {
             no: val.no.clone(),
             name: val.name.clone(),
             pronounce: val.pronounce.clone(),
+            reading: ReadingKeys::from_pronounce(&val.pronounce),
             artist: val.artist.clone(),
             card_type: val.card_type.clone(),
             klass: OptionString::empty(),
@@ -498,63 +1017,30 @@ impl From<PieceRelay> for Card {
             user: val.user.clone(),
             time: val.time.clone(),
             story: val.story.clone(),
+            is_dissona: val.story.is_dissona(),
             format: val.format.clone(),
             rarity: val.rarity.clone(),
             skill: val.skill.clone(),
             features: val.features.clone(),
+            feature_details: FeatureDetail::from_skill_lines(&val.skill.value, &val.features),
+            granted_skills: val.granted_skills.clone(),
+            life_burst: val.life_burst.clone(),
+            original: None,
+            image: None,
 
         }
     }
 }
 
 impl WixossCard for PieceRelay {
-    fn from_source(source: String) -> Self {
-        let document: Html = Html::parse_document(&source);
-
-        let selector_card_num = Selector::parse(".cardNum").unwrap();
-        let card_no = match document.select(&selector_card_num).next() {
-            Some(card_no) => card_no.inner_html(),
-            None => "unknown".into()
-        };
-
-        let selector_card_name = Selector::parse(".cardName").unwrap();
-        let card_name = match document.select(&selector_card_name).next() {
-            Some(card_name) => element_to_name_and_pronounce(card_name.inner_html()),
-            None => ("unknown".into(), "unknown".into())
-        };
-
-        let selector_rarity = Selector::parse(".cardRarity").unwrap();
-        let card_rarity = match document.select(&selector_rarity).next() {
-            Some(card_rarity) => card_rarity.inner_html(),
-            None => "unknown rarity".into()
-        };
-
-        let selector_artist = Selector::parse(".cardImg p span").unwrap();
-        let artist = match document.select(&selector_artist).next() {
-            Some(artist) => artist.inner_html(),
-            None => "unknown artist".into()
-        };
-
-        let selector_card_data = Selector::parse(".cardData dd").unwrap();
-
-        let mut card_data: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_data) {
-            card_data.push(element.inner_html());
-        }
-
-        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
-        let mut card_skills: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_skill) {
-            card_skills.push(element.inner_html());
-        }
-
-        let (skill, features) = parse_card_skill(card_skills.clone());
+    fn from_page(page: &CardPage) -> Self {
+        let card_data = &page.data;
 
         Self {
-            no: card_no,
-            name: card_name.0,
-            pronounce: card_name.1,
-            artist,
+            no: page.no.clone(),
+            name: page.name.clone(),
+            pronounce: page.pronounce.clone(),
+            artist: page.artist.clone(),
             card_type: CardType::PieceRelay,
             color: card_data[2].clone(),
             cost: OptionString::from_string(flatten_break(card_data[5].clone())),
@@ -562,9 +1048,11 @@ impl WixossCard for PieceRelay {
             time: split_by_break(card_data[9].clone()),
             story: parse_story(card_data[11].clone().trim().to_string()),
             format: parse_format(card_data[10].clone()),
-            rarity: card_rarity,
-            skill,
-            features,
+            rarity: page.rarity.clone(),
+            skill: page.skill.clone(),
+            features: page.features.clone(),
+            granted_skills: page.granted_skills.clone(),
+            life_burst: page.life_burst.clone(),
         }
     }
 }
@@ -587,6 +1075,9 @@ impl Display for PieceRelay {
         writeln!(f, "フォーマット\t:{}", self.format)?;
         writeln!(f, "レアリティ\t:{}", self.rarity)?;
         writeln!(f, "テキスト({})\t:{}", self.skill.value.len(), self.skill)?;
+        if let Some(life_burst) = &self.life_burst {
+            writeln!(f, "ライフバースト\t:{}", life_burst)?;
+        }
         writeln!(f, "フィーチャー({})\t:{:?}", self.features.len(), self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "))?;
         write!(f, "")
     }
@@ -594,7 +1085,7 @@ impl Display for PieceRelay {
 
 #[derive(Debug)]
 pub struct Key {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
     artist: String,
@@ -606,11 +1097,13 @@ pub struct Key {
     // power: Option<String>,
     user: OptionString,
     time: Vec<String>,
-    story: OptionString,
+    story: Story,
     format: Format,
     rarity: String,
     skill: Skills,
     features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
 }
 
 impl From<Key> for Card {
@@ -619,6 +1112,7 @@ impl From<Key> for Card {
             no: val.no.clone(),
             name: val.name.clone(),
             pronounce: val.pronounce.clone(),
+            reading: ReadingKeys::from_pronounce(&val.pronounce),
             artist: val.artist.clone(),
             card_type: val.card_type.clone(),
             klass: OptionString::empty(),
@@ -630,62 +1124,29 @@ impl From<Key> for Card {
             user: val.user.clone(),
             time: val.time.clone(),
             story: val.story.clone(),
+            is_dissona: val.story.is_dissona(),
             format: val.format.clone(),
             rarity: val.rarity.clone(),
             skill: val.skill.clone(),
             features: val.features.clone(),
+            feature_details: FeatureDetail::from_skill_lines(&val.skill.value, &val.features),
+            granted_skills: val.granted_skills.clone(),
+            life_burst: val.life_burst.clone(),
+            original: None,
+            image: None,
         }
     }
 }
 
 impl WixossCard for Key {
-    fn from_source(source: String) -> Self {
-        let document: Html = Html::parse_document(&source);
-
-        let selector_card_num = Selector::parse(".cardNum").unwrap();
-        let card_no = match document.select(&selector_card_num).next() {
-            Some(card_no) => card_no.inner_html(),
-            None => "unknown".into()
-        };
-
-        let selector_card_name = Selector::parse(".cardName").unwrap();
-        let card_name = match document.select(&selector_card_name).next() {
-            Some(card_name) => element_to_name_and_pronounce(card_name.inner_html()),
-            None => ("unknown".into(), "unknown".into())
-        };
-
-        let selector_rarity = Selector::parse(".cardRarity").unwrap();
-        let card_rarity = match document.select(&selector_rarity).next() {
-            Some(card_rarity) => card_rarity.inner_html(),
-            None => "unknown rarity".into()
-        };
-
-        let selector_artist = Selector::parse(".cardImg p span").unwrap();
-        let artist = match document.select(&selector_artist).next() {
-            Some(artist) => artist.inner_html(),
-            None => "unknown artist".into()
-        };
-
-        let selector_card_data = Selector::parse(".cardData dd").unwrap();
-
-        let mut card_data: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_data) {
-            card_data.push(element.inner_html());
-        }
-
-        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
-        let mut card_skills: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_skill) {
-            card_skills.push(element.inner_html());
-        }
-
-        let (skill, features) = parse_card_skill(card_skills.clone());
+    fn from_page(page: &CardPage) -> Self {
+        let card_data = &page.data;
 
         Self {
-            no: card_no,
-            name: card_name.0,
-            pronounce: card_name.1,
-            artist,
+            no: page.no.clone(),
+            name: page.name.clone(),
+            pronounce: page.pronounce.clone(),
+            artist: page.artist.clone(),
             card_type: CardType::Key,
             color: card_data[2].clone(),
             cost: OptionString::from_string(flatten_break(card_data[5].clone())),
@@ -693,9 +1154,11 @@ impl WixossCard for Key {
             time: split_by_break(card_data[9].clone()),
             story: parse_story(card_data[11].clone().trim().to_string()),
             format: parse_format(card_data[10].clone()),
-            rarity: card_rarity,
-            skill,
-            features,
+            rarity: page.rarity.clone(),
+            skill: page.skill.clone(),
+            features: page.features.clone(),
+            granted_skills: page.granted_skills.clone(),
+            life_burst: page.life_burst.clone(),
         }
     }
 }
@@ -718,6 +1181,9 @@ impl Display for Key {
         writeln!(f, "フォーマット\t:{}", self.format)?;
         writeln!(f, "レアリティ\t:{}", self.rarity)?;
         writeln!(f, "テキスト({})\t:{}", self.skill.value.len(), self.skill)?;
+        if let Some(life_burst) = &self.life_burst {
+            writeln!(f, "ライフバースト\t:{}", life_burst)?;
+        }
         writeln!(f, "フィーチャー({})\t:{:?}", self.features.len(), self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "))?;
         write!(f, "")
     }
@@ -725,7 +1191,7 @@ impl Display for Key {
 
 #[derive(Debug)]
 pub struct Signi {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
     artist: String,
@@ -739,11 +1205,13 @@ pub struct Signi {
     power: OptionString,
     user: OptionString,
     // time: OptionString,
-    story: OptionString,
+    story: Story,
     format: Format,
     rarity: String,
     skill: Skills,
     features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
 }
 
 impl From<Signi> for Card {
@@ -752,6 +1220,7 @@ impl From<Signi> for Card {
             no: val.no.clone(),
             name: val.name.clone(),
             pronounce: val.pronounce.clone(),
+            reading: ReadingKeys::from_pronounce(&val.pronounce),
             artist: val.artist.clone(),
             card_type: val.card_type.clone(),
             klass: val.klass.clone(),
@@ -763,62 +1232,29 @@ impl From<Signi> for Card {
             user: val.user.clone(),
             time: Vec::new(),
             story: val.story.clone(),
+            is_dissona: val.story.is_dissona(),
             format: val.format.clone(),
             rarity: val.rarity.clone(),
             skill: val.skill.clone(),
             features: val.features.clone(),
+            feature_details: FeatureDetail::from_skill_lines(&val.skill.value, &val.features),
+            granted_skills: val.granted_skills.clone(),
+            life_burst: val.life_burst.clone(),
+            original: None,
+            image: None,
         }
     }
 }
 
 impl WixossCard for Signi {
-    fn from_source(source: String) -> Self {
-        let document: Html = Html::parse_document(&source);
-
-        let selector_card_num = Selector::parse(".cardNum").unwrap();
-        let card_no = match document.select(&selector_card_num).next() {
-            Some(card_no) => card_no.inner_html(),
-            None => "unknown".into()
-        };
-
-        let selector_card_name = Selector::parse(".cardName").unwrap();
-        let card_name = match document.select(&selector_card_name).next() {
-            Some(card_name) => element_to_name_and_pronounce(card_name.inner_html()),
-            None => ("unknown".into(), "unknown".into())
-        };
-
-        let selector_rarity = Selector::parse(".cardRarity").unwrap();
-        let card_rarity = match document.select(&selector_rarity).next() {
-            Some(card_rarity) => card_rarity.inner_html(),
-            None => "unknown rarity".into()
-        };
-
-        let selector_artist = Selector::parse(".cardImg p span").unwrap();
-        let artist = match document.select(&selector_artist).next() {
-            Some(artist) => artist.inner_html(),
-            None => "unknown artist".into()
-        };
-
-        let selector_card_data = Selector::parse(".cardData dd").unwrap();
-
-        let mut card_data: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_data) {
-            card_data.push(element.inner_html());
-        }
-
-        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
-        let mut card_skills: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_skill) {
-            card_skills.push(element.inner_html());
-        }
-
-        let (skill, features) = parse_card_skill(card_skills.clone());
+    fn from_page(page: &CardPage) -> Self {
+        let card_data = &page.data;
 
         Self {
-            no: card_no,
-            name: card_name.0,
-            pronounce: card_name.1,
-            artist,
+            no: page.no.clone(),
+            name: page.name.clone(),
+            pronounce: page.pronounce.clone(),
+            artist: page.artist.clone(),
             card_type: CardType::Signi,
             klass: OptionString::from_string(card_data[1].clone()),
             color: card_data[2].clone(),
@@ -830,9 +1266,11 @@ impl WixossCard for Signi {
             // time: OptionString::from_string(card_data[9].clone()),
             story: parse_story(card_data[11].clone().trim().to_string()),
             format: parse_format(card_data[10].clone()),
-            rarity: card_rarity,
-            skill,
-            features,
+            rarity: page.rarity.clone(),
+            skill: page.skill.clone(),
+            features: page.features.clone(),
+            granted_skills: page.granted_skills.clone(),
+            life_burst: page.life_burst.clone(),
         }
     }
 }
@@ -854,6 +1292,9 @@ impl Display for Signi {
         writeln!(f, "フォーマット\t:{}", self.format)?;
         writeln!(f, "レアリティ\t:{}", self.rarity)?;
         writeln!(f, "テキスト({})\t:{}", self.skill.value.len(), self.skill)?;
+        if let Some(life_burst) = &self.life_burst {
+            writeln!(f, "ライフバースト\t:{}", life_burst)?;
+        }
         writeln!(f, "フィーチャー({})\t:{:?}", self.features.len(), self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "))?;
         write!(f, "")
     }
@@ -862,7 +1303,7 @@ impl Display for Signi {
 
 #[derive(Debug)]
 pub struct Spell {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
     artist: String,
@@ -874,11 +1315,13 @@ pub struct Spell {
     // power: OptionString,
     user: OptionString,
     // time: OptionString,
-    story: OptionString,
+    story: Story,
     format: Format,
     rarity: String,
     skill: Skills,
     features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
 }
 
 impl From<Spell> for Card {
@@ -887,6 +1330,7 @@ impl From<Spell> for Card {
             no: val.no.clone(),
             name: val.name.clone(),
             pronounce: val.pronounce.clone(),
+            reading: ReadingKeys::from_pronounce(&val.pronounce),
             artist: val.artist.clone(),
             card_type: val.card_type.clone(),
             klass: OptionString::empty(),
@@ -898,62 +1342,29 @@ impl From<Spell> for Card {
             user: val.user.clone(),
             time: Vec::new(),
             story: val.story.clone(),
+            is_dissona: val.story.is_dissona(),
             format: val.format.clone(),
             rarity: val.rarity.clone(),
             skill: val.skill.clone(),
             features: val.features.clone(),
+            feature_details: FeatureDetail::from_skill_lines(&val.skill.value, &val.features),
+            granted_skills: val.granted_skills.clone(),
+            life_burst: val.life_burst.clone(),
+            original: None,
+            image: None,
         }
     }
 }
 
 impl WixossCard for Spell {
-    fn from_source(source: String) -> Self {
-        let document: Html = Html::parse_document(&source);
-
-        let selector_card_num = Selector::parse(".cardNum").unwrap();
-        let card_no = match document.select(&selector_card_num).next() {
-            Some(card_no) => card_no.inner_html(),
-            None => "unknown".into()
-        };
-
-        let selector_card_name = Selector::parse(".cardName").unwrap();
-        let card_name = match document.select(&selector_card_name).next() {
-            Some(card_name) => element_to_name_and_pronounce(card_name.inner_html()),
-            None => ("unknown".into(), "unknown".into())
-        };
-
-        let selector_rarity = Selector::parse(".cardRarity").unwrap();
-        let card_rarity = match document.select(&selector_rarity).next() {
-            Some(card_rarity) => card_rarity.inner_html(),
-            None => "unknown rarity".into()
-        };
-
-        let selector_artist = Selector::parse(".cardImg p span").unwrap();
-        let artist = match document.select(&selector_artist).next() {
-            Some(artist) => artist.inner_html(),
-            None => "unknown artist".into()
-        };
-
-        let selector_card_data = Selector::parse(".cardData dd").unwrap();
-
-        let mut card_data: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_data) {
-            card_data.push(element.inner_html());
-        }
-
-        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
-        let mut card_skills: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_skill) {
-            card_skills.push(element.inner_html());
-        }
-
-        let (skill, features) = parse_card_skill(card_skills.clone());
+    fn from_page(page: &CardPage) -> Self {
+        let card_data = &page.data;
 
         Self {
-            no: card_no,
-            name: card_name.0,
-            pronounce: card_name.1,
-            artist,
+            no: page.no.clone(),
+            name: page.name.clone(),
+            pronounce: page.pronounce.clone(),
+            artist: page.artist.clone(),
             card_type: CardType::Spell,
             color: card_data[2].clone(),
             // level: OptionString::from_string(card_data[3].clone()),
@@ -964,9 +1375,11 @@ impl WixossCard for Spell {
             // time: OptionString::from_string(card_data[9].clone()),
             story: parse_story(card_data[11].clone().trim().to_string()),
             format: parse_format(card_data[10].clone()),
-            rarity: card_rarity,
-            skill,
-            features,
+            rarity: page.rarity.clone(),
+            skill: page.skill.clone(),
+            features: page.features.clone(),
+            granted_skills: page.granted_skills.clone(),
+            life_burst: page.life_burst.clone(),
         }
     }
 }
@@ -988,6 +1401,9 @@ impl Display for Spell {
         writeln!(f, "フォーマット\t:{}", self.format)?;
         writeln!(f, "レアリティ\t:{}", self.rarity)?;
         writeln!(f, "テキスト({})\t:{}", self.skill.value.len(), self.skill)?;
+        if let Some(life_burst) = &self.life_burst {
+            writeln!(f, "ライフバースト\t:{}", life_burst)?;
+        }
         writeln!(f, "フィーチャー({})\t:{:?}", self.features.len(), self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "))?;
         write!(f, "")
     }
@@ -995,7 +1411,7 @@ impl Display for Spell {
 
 #[derive(Debug)]
 pub struct SpellCraft {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
     artist: String,
@@ -1007,11 +1423,13 @@ pub struct SpellCraft {
     // power: OptionString,
     user: OptionString,
     // time: OptionString,
-    story: OptionString,
+    story: Story,
     format: Format,
     rarity: String,
     skill: Skills,
     features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
 }
 
 impl From<SpellCraft> for Card {
@@ -1020,6 +1438,7 @@ impl From<SpellCraft> for Card {
             no: val.no.clone(),
             name: val.name.clone(),
             pronounce: val.pronounce.clone(),
+            reading: ReadingKeys::from_pronounce(&val.pronounce),
             artist: val.artist.clone(),
             card_type: val.card_type.clone(),
             klass: OptionString::empty(),
@@ -1031,62 +1450,29 @@ impl From<SpellCraft> for Card {
             user: val.user.clone(),
             time: Vec::new(),
             story: val.story.clone(),
+            is_dissona: val.story.is_dissona(),
             format: val.format.clone(),
             rarity: val.rarity.clone(),
             skill: val.skill.clone(),
             features: val.features.clone(),
+            feature_details: FeatureDetail::from_skill_lines(&val.skill.value, &val.features),
+            granted_skills: val.granted_skills.clone(),
+            life_burst: val.life_burst.clone(),
+            original: None,
+            image: None,
         }
     }
 }
 
 impl WixossCard for SpellCraft {
-    fn from_source(source: String) -> Self {
-        let document: Html = Html::parse_document(&source);
-
-        let selector_card_num = Selector::parse(".cardNum").unwrap();
-        let card_no = match document.select(&selector_card_num).next() {
-            Some(card_no) => card_no.inner_html(),
-            None => "unknown".into()
-        };
-
-        let selector_card_name = Selector::parse(".cardName").unwrap();
-        let card_name = match document.select(&selector_card_name).next() {
-            Some(card_name) => element_to_name_and_pronounce(card_name.inner_html()),
-            None => ("unknown".into(), "unknown".into())
-        };
-
-        let selector_rarity = Selector::parse(".cardRarity").unwrap();
-        let card_rarity = match document.select(&selector_rarity).next() {
-            Some(card_rarity) => card_rarity.inner_html(),
-            None => "unknown rarity".into()
-        };
-
-        let selector_artist = Selector::parse(".cardImg p span").unwrap();
-        let artist = match document.select(&selector_artist).next() {
-            Some(artist) => artist.inner_html(),
-            None => "unknown artist".into()
-        };
-
-        let selector_card_data = Selector::parse(".cardData dd").unwrap();
-
-        let mut card_data: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_data) {
-            card_data.push(element.inner_html());
-        }
-
-        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
-        let mut card_skills: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_skill) {
-            card_skills.push(element.inner_html());
-        }
-
-        let (skill, features) = parse_card_skill(card_skills.clone());
+    fn from_page(page: &CardPage) -> Self {
+        let card_data = &page.data;
 
         Self {
-            no: card_no,
-            name: card_name.0,
-            pronounce: card_name.1,
-            artist,
+            no: page.no.clone(),
+            name: page.name.clone(),
+            pronounce: page.pronounce.clone(),
+            artist: page.artist.clone(),
             card_type: CardType::SpellCraft,
             color: card_data[2].clone(),
             // level: OptionString::from_string(card_data[3].clone()),
@@ -1097,9 +1483,11 @@ impl WixossCard for SpellCraft {
             // time: OptionString::from_string(card_data[9].clone()),
             story: parse_story(card_data[11].clone().trim().to_string()),
             format: parse_format(card_data[10].clone()),
-            rarity: card_rarity,
-            skill,
-            features,
+            rarity: page.rarity.clone(),
+            skill: page.skill.clone(),
+            features: page.features.clone(),
+            granted_skills: page.granted_skills.clone(),
+            life_burst: page.life_burst.clone(),
         }
     }
 }
@@ -1121,6 +1509,9 @@ impl Display for SpellCraft {
         writeln!(f, "フォーマット\t:{}", self.format)?;
         writeln!(f, "レアリティ\t:{}", self.rarity)?;
         writeln!(f, "テキスト({})\t:{}", self.skill.value.len(), self.skill)?;
+        if let Some(life_burst) = &self.life_burst {
+            writeln!(f, "ライフバースト\t:{}", life_burst)?;
+        }
         writeln!(f, "フィーチャー({})\t:{:?}", self.features.len(), self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "))?;
         write!(f, "")
     }
@@ -1129,7 +1520,7 @@ impl Display for SpellCraft {
 
 #[derive(Debug)]
 pub struct Lrig {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
     artist: String,
@@ -1142,11 +1533,13 @@ pub struct Lrig {
     // power: OptionString,
     user: OptionString,
     // time: OptionString,
-    story: OptionString,
+    story: Story,
     format: Format,
     rarity: String,
     skill: Skills,
     features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
 }
 
 impl From<Lrig> for Card {
@@ -1155,6 +1548,7 @@ impl From<Lrig> for Card {
             no: val.no.clone(),
             name: val.name.clone(),
             pronounce: val.pronounce.clone(),
+            reading: ReadingKeys::from_pronounce(&val.pronounce),
             artist: val.artist.clone(),
             card_type: val.card_type.clone(),
             klass: OptionString::empty(),
@@ -1166,62 +1560,29 @@ impl From<Lrig> for Card {
             user: val.user.clone(),
             time: Vec::new(),
             story: val.story.clone(),
+            is_dissona: val.story.is_dissona(),
             format: val.format.clone(),
             rarity: val.rarity.clone(),
             skill: val.skill.clone(),
             features: val.features.clone(),
+            feature_details: FeatureDetail::from_skill_lines(&val.skill.value, &val.features),
+            granted_skills: val.granted_skills.clone(),
+            life_burst: val.life_burst.clone(),
+            original: None,
+            image: None,
         }
     }
 }
 
 impl WixossCard for Lrig {
-    fn from_source(source: String) -> Self {
-        let document: Html = Html::parse_document(&source);
-
-        let selector_card_num = Selector::parse(".cardNum").unwrap();
-        let card_no = match document.select(&selector_card_num).next() {
-            Some(card_no) => card_no.inner_html(),
-            None => "unknown".into()
-        };
-
-        let selector_card_name = Selector::parse(".cardName").unwrap();
-        let card_name = match document.select(&selector_card_name).next() {
-            Some(card_name) => element_to_name_and_pronounce(card_name.inner_html()),
-            None => ("unknown".into(), "unknown".into())
-        };
-
-        let selector_rarity = Selector::parse(".cardRarity").unwrap();
-        let card_rarity = match document.select(&selector_rarity).next() {
-            Some(card_rarity) => card_rarity.inner_html(),
-            None => "unknown rarity".into()
-        };
-
-        let selector_artist = Selector::parse(".cardImg p span").unwrap();
-        let artist = match document.select(&selector_artist).next() {
-            Some(artist) => artist.inner_html(),
-            None => "unknown artist".into()
-        };
-
-        let selector_card_data = Selector::parse(".cardData dd").unwrap();
-
-        let mut card_data: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_data) {
-            card_data.push(element.inner_html());
-        }
-
-        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
-        let mut card_skills: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_skill) {
-            card_skills.push(element.inner_html());
-        }
-
-        let (skill, features) = parse_card_skill(card_skills.clone());
+    fn from_page(page: &CardPage) -> Self {
+        let card_data = &page.data;
 
         Self {
-            no: card_no,
-            name: card_name.0,
-            pronounce: card_name.1,
-            artist,
+            no: page.no.clone(),
+            name: page.name.clone(),
+            pronounce: page.pronounce.clone(),
+            artist: page.artist.clone(),
             card_type: CardType::Lrig,
             color: card_data[2].clone(),
             level: OptionString::from_string(card_data[3].clone()),
@@ -1232,9 +1593,11 @@ impl WixossCard for Lrig {
             // time: OptionString::from_string(card_data[9].clone()),
             story: parse_story(card_data[11].clone().trim().to_string()),
             format: parse_format(card_data[10].clone()),
-            rarity: card_rarity,
-            skill,
-            features,
+            rarity: page.rarity.clone(),
+            skill: page.skill.clone(),
+            features: page.features.clone(),
+            granted_skills: page.granted_skills.clone(),
+            life_burst: page.life_burst.clone(),
         }
     }
 }
@@ -1256,6 +1619,9 @@ impl Display for Lrig {
         writeln!(f, "フォーマット\t:{}", self.format)?;
         writeln!(f, "レアリティ\t:{}", self.rarity)?;
         writeln!(f, "テキスト({})\t:{}", self.skill.value.len(), self.skill)?;
+        if let Some(life_burst) = &self.life_burst {
+            writeln!(f, "ライフバースト\t:{}", life_burst)?;
+        }
         writeln!(f, "フィーチャー({})\t:{:?}", self.features.len(), self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "))?;
         write!(f, "")
     }
@@ -1263,7 +1629,7 @@ impl Display for Lrig {
 
 #[derive(Debug)]
 pub struct LrigAssist {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
     artist: String,
@@ -1276,11 +1642,13 @@ pub struct LrigAssist {
     // power: OptionString,
     user: OptionString,
     time: Vec<String>,
-    story: OptionString,
+    story: Story,
     format: Format,
     rarity: String,
     skill: Skills,
     features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
 }
 
 impl From<LrigAssist> for Card {
@@ -1289,6 +1657,7 @@ impl From<LrigAssist> for Card {
             no: val.no.clone(),
             name: val.name.clone(),
             pronounce: val.pronounce.clone(),
+            reading: ReadingKeys::from_pronounce(&val.pronounce),
             artist: val.artist.clone(),
             card_type: val.card_type.clone(),
             klass: OptionString::empty(),
@@ -1300,62 +1669,29 @@ impl From<LrigAssist> for Card {
             user: val.user.clone(),
             time: val.time.clone(),
             story: val.story.clone(),
+            is_dissona: val.story.is_dissona(),
             format: val.format.clone(),
             rarity: val.rarity.clone(),
             skill: val.skill.clone(),
             features: val.features.clone(),
+            feature_details: FeatureDetail::from_skill_lines(&val.skill.value, &val.features),
+            granted_skills: val.granted_skills.clone(),
+            life_burst: val.life_burst.clone(),
+            original: None,
+            image: None,
         }
     }
 }
 
 impl WixossCard for LrigAssist {
-    fn from_source(source: String) -> Self {
-        let document: Html = Html::parse_document(&source);
-
-        let selector_card_num = Selector::parse(".cardNum").unwrap();
-        let card_no = match document.select(&selector_card_num).next() {
-            Some(card_no) => card_no.inner_html(),
-            None => "unknown".into()
-        };
-
-        let selector_card_name = Selector::parse(".cardName").unwrap();
-        let card_name = match document.select(&selector_card_name).next() {
-            Some(card_name) => element_to_name_and_pronounce(card_name.inner_html()),
-            None => ("unknown".into(), "unknown".into())
-        };
-
-        let selector_rarity = Selector::parse(".cardRarity").unwrap();
-        let card_rarity = match document.select(&selector_rarity).next() {
-            Some(card_rarity) => card_rarity.inner_html(),
-            None => "unknown rarity".into()
-        };
-
-        let selector_artist = Selector::parse(".cardImg p span").unwrap();
-        let artist = match document.select(&selector_artist).next() {
-            Some(artist) => artist.inner_html(),
-            None => "unknown artist".into()
-        };
-
-        let selector_card_data = Selector::parse(".cardData dd").unwrap();
-
-        let mut card_data: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_data) {
-            card_data.push(element.inner_html());
-        }
-
-        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
-        let mut card_skills: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_skill) {
-            card_skills.push(element.inner_html());
-        }
-
-        let (skill, features) = parse_card_skill(card_skills.clone());
+    fn from_page(page: &CardPage) -> Self {
+        let card_data = &page.data;
 
         Self {
-            no: card_no,
-            name: card_name.0,
-            pronounce: card_name.1,
-            artist,
+            no: page.no.clone(),
+            name: page.name.clone(),
+            pronounce: page.pronounce.clone(),
+            artist: page.artist.clone(),
             card_type: CardType::LrigAssist,
             color: card_data[2].clone(),
             level: OptionString::from_string(card_data[3].clone()),
@@ -1366,9 +1702,11 @@ impl WixossCard for LrigAssist {
             time: split_by_break(card_data[9].clone()),
             story: parse_story(card_data[11].clone().trim().to_string()),
             format: parse_format(card_data[10].clone()),
-            rarity: card_rarity,
-            skill,
-            features,
+            rarity: page.rarity.clone(),
+            skill: page.skill.clone(),
+            features: page.features.clone(),
+            granted_skills: page.granted_skills.clone(),
+            life_burst: page.life_burst.clone(),
         }
     }
 }
@@ -1391,6 +1729,9 @@ impl Display for LrigAssist {
         writeln!(f, "フォーマット\t:{}", self.format)?;
         writeln!(f, "レアリティ\t:{}", self.rarity)?;
         writeln!(f, "テキスト({})\t:{}", self.skill.value.len(), self.skill)?;
+        if let Some(life_burst) = &self.life_burst {
+            writeln!(f, "ライフバースト\t:{}", life_burst)?;
+        }
         writeln!(f, "フィーチャー({})\t:{:?}", self.features.len(), self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "))?;
         write!(f, "")
     }
@@ -1399,7 +1740,7 @@ impl Display for LrigAssist {
 
 #[derive(Debug)]
 pub struct Arts {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
     artist: String,
@@ -1412,11 +1753,13 @@ pub struct Arts {
     // power: OptionString,
     user: OptionString,
     time: Vec<String>,
-    story: OptionString,
+    story: Story,
     format: Format,
     rarity: String,
     skill: Skills,
     features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
 }
 
 impl From<Arts> for Card {
@@ -1425,6 +1768,7 @@ impl From<Arts> for Card {
             no: val.no.clone(),
             name: val.name.clone(),
             pronounce: val.pronounce.clone(),
+            reading: ReadingKeys::from_pronounce(&val.pronounce),
             artist: val.artist.clone(),
             card_type: val.card_type.clone(),
             klass: OptionString::empty(),
@@ -1436,62 +1780,29 @@ impl From<Arts> for Card {
             user: val.user.clone(),
             time: val.time.clone(),
             story: val.story.clone(),
+            is_dissona: val.story.is_dissona(),
             format: val.format.clone(),
             rarity: val.rarity.clone(),
             skill: val.skill.clone(),
             features: val.features.clone(),
+            feature_details: FeatureDetail::from_skill_lines(&val.skill.value, &val.features),
+            granted_skills: val.granted_skills.clone(),
+            life_burst: val.life_burst.clone(),
+            original: None,
+            image: None,
         }
     }
 }
 
 impl WixossCard for Arts {
-    fn from_source(source: String) -> Self {
-        let document: Html = Html::parse_document(&source);
-
-        let selector_card_num = Selector::parse(".cardNum").unwrap();
-        let card_no = match document.select(&selector_card_num).next() {
-            Some(card_no) => card_no.inner_html(),
-            None => "unknown".into()
-        };
-
-        let selector_card_name = Selector::parse(".cardName").unwrap();
-        let card_name = match document.select(&selector_card_name).next() {
-            Some(card_name) => element_to_name_and_pronounce(card_name.inner_html()),
-            None => ("unknown".into(), "unknown".into())
-        };
-
-        let selector_rarity = Selector::parse(".cardRarity").unwrap();
-        let card_rarity = match document.select(&selector_rarity).next() {
-            Some(card_rarity) => card_rarity.inner_html(),
-            None => "unknown rarity".into()
-        };
-
-        let selector_artist = Selector::parse(".cardImg p span").unwrap();
-        let artist = match document.select(&selector_artist).next() {
-            Some(artist) => artist.inner_html(),
-            None => "unknown artist".into()
-        };
-
-        let selector_card_data = Selector::parse(".cardData dd").unwrap();
-
-        let mut card_data: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_data) {
-            card_data.push(element.inner_html());
-        }
-
-        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
-        let mut card_skills: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_skill) {
-            card_skills.push(element.inner_html());
-        }
-
-        let (skill, features) = parse_card_skill(card_skills.clone());
+    fn from_page(page: &CardPage) -> Self {
+        let card_data = &page.data;
 
         Self {
-            no: card_no,
-            name: card_name.0,
-            pronounce: card_name.1,
-            artist,
+            no: page.no.clone(),
+            name: page.name.clone(),
+            pronounce: page.pronounce.clone(),
+            artist: page.artist.clone(),
             card_type: CardType::Arts,
             color: card_data[2].clone(),
             cost: OptionString::from_string(flatten_break(card_data[5].clone())),
@@ -1499,9 +1810,11 @@ impl WixossCard for Arts {
             time: split_by_break(card_data[9].clone()),
             story: parse_story(card_data[11].clone().trim().to_string()),
             format: parse_format(card_data[10].clone()),
-            rarity: card_rarity,
-            skill,
-            features,
+            rarity: page.rarity.clone(),
+            skill: page.skill.clone(),
+            features: page.features.clone(),
+            granted_skills: page.granted_skills.clone(),
+            life_burst: page.life_burst.clone(),
         }
     }
 }
@@ -1524,6 +1837,9 @@ impl Display for Arts {
         writeln!(f, "フォーマット\t:{}", self.format)?;
         writeln!(f, "レアリティ\t:{}", self.rarity)?;
         writeln!(f, "テキスト({})\t:{}", self.skill.value.len(), self.skill)?;
+        if let Some(life_burst) = &self.life_burst {
+            writeln!(f, "ライフバースト\t:{}", life_burst)?;
+        }
         writeln!(f, "フィーチャー({})\t:{:?}", self.features.len(), self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "))?;
         write!(f, "")
     }
@@ -1531,7 +1847,7 @@ impl Display for Arts {
 
 #[derive(Debug)]
 pub struct Resona {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
     artist: String,
@@ -1544,11 +1860,13 @@ pub struct Resona {
     power: OptionString,
     user: OptionString,
     time: Vec<String>,
-    story: OptionString,
+    story: Story,
     format: Format,
     rarity: String,
     skill: Skills,
     features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
 }
 
 impl From<Resona> for Card {
@@ -1557,6 +1875,7 @@ impl From<Resona> for Card {
             no: val.no.clone(),
             name: val.name.clone(),
             pronounce: val.pronounce.clone(),
+            reading: ReadingKeys::from_pronounce(&val.pronounce),
             artist: val.artist.clone(),
             card_type: val.card_type.clone(),
             klass: val.klass.clone(),
@@ -1568,64 +1887,29 @@ impl From<Resona> for Card {
             user: val.user.clone(),
             time: val.time.clone(),
             story: val.story.clone(),
+            is_dissona: val.story.is_dissona(),
             format: val.format.clone(),
             rarity: val.rarity.clone(),
             skill: val.skill.clone(),
             features: val.features.clone(),
+            feature_details: FeatureDetail::from_skill_lines(&val.skill.value, &val.features),
+            granted_skills: val.granted_skills.clone(),
+            life_burst: val.life_burst.clone(),
+            original: None,
+            image: None,
         }
     }
 }
 
 impl WixossCard for Resona {
-    fn from_source(source: String) -> Self {
-        let document: Html = Html::parse_document(&source);
-
-        let selector_card_num = Selector::parse(".cardNum").unwrap();
-        let card_no = match document.select(&selector_card_num).next() {
-            Some(card_no) => card_no.inner_html(),
-            None => "unknown".into()
-        };
-
-        let selector_card_name = Selector::parse(".cardName").unwrap();
-        let card_name = match document.select(&selector_card_name).next() {
-            Some(card_name) => element_to_name_and_pronounce(card_name.inner_html()),
-            None => ("unknown".into(), "unknown".into())
-        };
-
-        let selector_rarity = Selector::parse(".cardRarity").unwrap();
-        let card_rarity = match document.select(&selector_rarity).next() {
-            Some(card_rarity) => card_rarity.inner_html(),
-            None => "unknown rarity".into()
-        };
-
-        let selector_artist = Selector::parse(".cardImg p span").unwrap();
-        let artist = match document.select(&selector_artist).next() {
-            Some(artist) => artist.inner_html(),
-            None => "unknown artist".into()
-        };
-
-        let selector_card_data = Selector::parse(".cardData dd").unwrap();
-
-        let mut card_data: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_data) {
-            card_data.push(element.inner_html());
-        }
-
-        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
-        let mut card_skills: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_skill) {
-            card_skills.push(element.inner_html());
-        }
-
-        let (skill, features) = parse_card_skill(card_skills.clone());
-
-        // todo: 出現条件とタイミングがSkillにあるので詳細にパースする必要あり
+    fn from_page(page: &CardPage) -> Self {
+        let card_data = &page.data;
 
         Self {
-            no: card_no,
-            name: card_name.0,
-            pronounce: card_name.1,
-            artist,
+            no: page.no.clone(),
+            name: page.name.clone(),
+            pronounce: page.pronounce.clone(),
+            artist: page.artist.clone(),
             card_type: CardType::Resona,
             klass: OptionString::from_string(card_data[1].clone()),
             color: card_data[2].clone(),
@@ -1636,9 +1920,11 @@ impl WixossCard for Resona {
             time: split_by_break(card_data[9].clone()),
             story: parse_story(card_data[11].clone().trim().to_string()),
             format: parse_format(card_data[10].clone()),
-            rarity: card_rarity,
-            skill,
-            features,
+            rarity: page.rarity.clone(),
+            skill: page.skill.clone(),
+            features: page.features.clone(),
+            granted_skills: page.granted_skills.clone(),
+            life_burst: page.life_burst.clone(),
         }
     }
 }
@@ -1662,6 +1948,9 @@ impl Display for Resona {
         writeln!(f, "フォーマット\t:{}", self.format)?;
         writeln!(f, "レアリティ\t:{}", self.rarity)?;
         writeln!(f, "テキスト({})\t:{}", self.skill.value.len(), self.skill)?;
+        if let Some(life_burst) = &self.life_burst {
+            writeln!(f, "ライフバースト\t:{}", life_burst)?;
+        }
         writeln!(f, "フィーチャー({})\t:{:?}", self.features.len(), self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "))?;
         write!(f, "")
     }
@@ -1669,7 +1958,7 @@ impl Display for Resona {
 
 #[derive(Debug)]
 pub struct ResonaCraft {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
     artist: String,
@@ -1682,11 +1971,13 @@ pub struct ResonaCraft {
     power: OptionString,
     user: OptionString,
     time: Vec<String>,
-    story: OptionString,
+    story: Story,
     format: Format,
     rarity: String,
     skill: Skills,
     features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
 }
 
 impl From<ResonaCraft> for Card {
@@ -1695,6 +1986,7 @@ impl From<ResonaCraft> for Card {
             no: val.no.clone(),
             name: val.name.clone(),
             pronounce: val.pronounce.clone(),
+            reading: ReadingKeys::from_pronounce(&val.pronounce),
             artist: val.artist.clone(),
             card_type: val.card_type.clone(),
             klass: val.klass.clone(),
@@ -1706,64 +1998,29 @@ impl From<ResonaCraft> for Card {
             user: val.user.clone(),
             time: val.time.clone(),
             story: val.story.clone(),
+            is_dissona: val.story.is_dissona(),
             format: val.format.clone(),
             rarity: val.rarity.clone(),
             skill: val.skill.clone(),
             features: val.features.clone(),
+            feature_details: FeatureDetail::from_skill_lines(&val.skill.value, &val.features),
+            granted_skills: val.granted_skills.clone(),
+            life_burst: val.life_burst.clone(),
+            original: None,
+            image: None,
         }
     }
 }
 
 impl WixossCard for ResonaCraft {
-    fn from_source(source: String) -> Self {
-        let document: Html = Html::parse_document(&source);
-
-        let selector_card_num = Selector::parse(".cardNum").unwrap();
-        let card_no = match document.select(&selector_card_num).next() {
-            Some(card_no) => card_no.inner_html(),
-            None => "unknown".into()
-        };
-
-        let selector_card_name = Selector::parse(".cardName").unwrap();
-        let card_name = match document.select(&selector_card_name).next() {
-            Some(card_name) => element_to_name_and_pronounce(card_name.inner_html()),
-            None => ("unknown".into(), "unknown".into())
-        };
-
-        let selector_rarity = Selector::parse(".cardRarity").unwrap();
-        let card_rarity = match document.select(&selector_rarity).next() {
-            Some(card_rarity) => card_rarity.inner_html(),
-            None => "unknown rarity".into()
-        };
-
-        let selector_artist = Selector::parse(".cardImg p span").unwrap();
-        let artist = match document.select(&selector_artist).next() {
-            Some(artist) => artist.inner_html(),
-            None => "unknown artist".into()
-        };
-
-        let selector_card_data = Selector::parse(".cardData dd").unwrap();
-
-        let mut card_data: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_data) {
-            card_data.push(element.inner_html());
-        }
-
-        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
-        let mut card_skills: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_skill) {
-            card_skills.push(element.inner_html());
-        }
-
-        let (skill, features) = parse_card_skill(card_skills.clone());
-
-        // todo: 出現条件とタイミングがSkillにあるので詳細にパースする必要あり
+    fn from_page(page: &CardPage) -> Self {
+        let card_data = &page.data;
 
         Self {
-            no: card_no,
-            name: card_name.0,
-            pronounce: card_name.1,
-            artist,
+            no: page.no.clone(),
+            name: page.name.clone(),
+            pronounce: page.pronounce.clone(),
+            artist: page.artist.clone(),
             card_type: CardType::ResonaCraft,
             klass: OptionString::from_string(card_data[1].clone()),
             color: card_data[2].clone(),
@@ -1774,9 +2031,11 @@ impl WixossCard for ResonaCraft {
             time: split_by_break(card_data[9].clone()),
             story: parse_story(card_data[11].clone().trim().to_string()),
             format: parse_format(card_data[10].clone()),
-            rarity: card_rarity,
-            skill,
-            features,
+            rarity: page.rarity.clone(),
+            skill: page.skill.clone(),
+            features: page.features.clone(),
+            granted_skills: page.granted_skills.clone(),
+            life_burst: page.life_burst.clone(),
         }
     }
 }
@@ -1800,6 +2059,9 @@ impl Display for ResonaCraft {
         writeln!(f, "フォーマット\t:{}", self.format)?;
         writeln!(f, "レアリティ\t:{}", self.rarity)?;
         writeln!(f, "テキスト({})\t:{}", self.skill.value.len(), self.skill)?;
+        if let Some(life_burst) = &self.life_burst {
+            writeln!(f, "ライフバースト\t:{}", life_burst)?;
+        }
         writeln!(f, "フィーチャー({})\t:{:?}", self.features.len(), self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "))?;
         write!(f, "")
     }
@@ -1807,7 +2069,7 @@ impl Display for ResonaCraft {
 
 #[derive(Debug)]
 pub struct ArtsCraft {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
     artist: String,
@@ -1820,11 +2082,13 @@ pub struct ArtsCraft {
     // power: OptionString,
     user: OptionString,
     time: Vec<String>,
-    story: OptionString,
+    story: Story,
     format: Format,
     rarity: String,
     skill: Skills,
     features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
 }
 
 impl From<ArtsCraft> for Card {
@@ -1833,6 +2097,7 @@ impl From<ArtsCraft> for Card {
             no: val.no.clone(),
             name: val.name.clone(),
             pronounce: val.pronounce.clone(),
+            reading: ReadingKeys::from_pronounce(&val.pronounce),
             artist: val.artist.clone(),
             card_type: val.card_type.clone(),
             klass: OptionString::empty(),
@@ -1844,62 +2109,29 @@ impl From<ArtsCraft> for Card {
             user: val.user.clone(),
             time: val.time.clone(),
             story: val.story.clone(),
+            is_dissona: val.story.is_dissona(),
             format: val.format.clone(),
             rarity: val.rarity.clone(),
             skill: val.skill.clone(),
             features: val.features.clone(),
+            feature_details: FeatureDetail::from_skill_lines(&val.skill.value, &val.features),
+            granted_skills: val.granted_skills.clone(),
+            life_burst: val.life_burst.clone(),
+            original: None,
+            image: None,
         }
     }
 }
 
 impl WixossCard for ArtsCraft {
-    fn from_source(source: String) -> Self {
-        let document: Html = Html::parse_document(&source);
-
-        let selector_card_num = Selector::parse(".cardNum").unwrap();
-        let card_no = match document.select(&selector_card_num).next() {
-            Some(card_no) => card_no.inner_html(),
-            None => "unknown".into()
-        };
-
-        let selector_card_name = Selector::parse(".cardName").unwrap();
-        let card_name = match document.select(&selector_card_name).next() {
-            Some(card_name) => element_to_name_and_pronounce(card_name.inner_html()),
-            None => ("unknown".into(), "unknown".into())
-        };
-
-        let selector_rarity = Selector::parse(".cardRarity").unwrap();
-        let card_rarity = match document.select(&selector_rarity).next() {
-            Some(card_rarity) => card_rarity.inner_html(),
-            None => "unknown rarity".into()
-        };
-
-        let selector_artist = Selector::parse(".cardImg p span").unwrap();
-        let artist = match document.select(&selector_artist).next() {
-            Some(artist) => artist.inner_html(),
-            None => "unknown artist".into()
-        };
-
-        let selector_card_data = Selector::parse(".cardData dd").unwrap();
-
-        let mut card_data: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_data) {
-            card_data.push(element.inner_html());
-        }
-
-        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
-        let mut card_skills: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_skill) {
-            card_skills.push(element.inner_html());
-        }
-
-        let (skill, features) = parse_card_skill(card_skills.clone());
+    fn from_page(page: &CardPage) -> Self {
+        let card_data = &page.data;
 
         Self {
-            no: card_no,
-            name: card_name.0,
-            pronounce: card_name.1,
-            artist,
+            no: page.no.clone(),
+            name: page.name.clone(),
+            pronounce: page.pronounce.clone(),
+            artist: page.artist.clone(),
             card_type: CardType::ArtsCraft,
             color: card_data[2].clone(),
             cost: OptionString::from_string(flatten_break(card_data[5].clone())),
@@ -1907,9 +2139,11 @@ impl WixossCard for ArtsCraft {
             time: split_by_break(card_data[9].clone()),
             story: parse_story(card_data[11].clone().trim().to_string()),
             format: parse_format(card_data[10].clone()),
-            rarity: card_rarity,
-            skill,
-            features,
+            rarity: page.rarity.clone(),
+            skill: page.skill.clone(),
+            features: page.features.clone(),
+            granted_skills: page.granted_skills.clone(),
+            life_burst: page.life_burst.clone(),
         }
     }
 }
@@ -1932,41 +2166,147 @@ impl Display for ArtsCraft {
         writeln!(f, "フォーマット\t:{}", self.format)?;
         writeln!(f, "レアリティ\t:{}", self.rarity)?;
         writeln!(f, "テキスト({})\t:{}", self.skill.value.len(), self.skill)?;
+        if let Some(life_burst) = &self.life_burst {
+            writeln!(f, "ライフバースト\t:{}", life_burst)?;
+        }
         writeln!(f, "フィーチャー({})\t:{:?}", self.features.len(), self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "))?;
         write!(f, "")
     }
 }
 
 
-fn parse_card_skill(source: Vec<String>) -> (Skills, HashSet<CardFeature>) {
-    let re_br = Regex::new(r"<br\s?>").unwrap();
+fn parse_card_skill(lines: &[SkillLine]) -> (Skills, HashSet<CardFeature>, Vec<Ability>, Option<Ability>) {
     let mut features: HashSet<CardFeature> = HashSet::new();
     let mut all_skills: Vec<String> = Vec::new();
+    let mut granted_skills: Vec<Ability> = Vec::new();
+    let mut life_burst_lines: Vec<String> = Vec::new();
+    let mut life_burst_features: Vec<CardFeature> = Vec::new();
+
+    for SkillLine { text: line, features: features_detected, kind, .. } in lines.iter().cloned() {
+        match kind {
+            // 他のカードに与える能力のフィーチャーは、このカード自身のフィーチャーには含めない
+            SkillLineKind::Granted => {
+                if !line.is_empty() {
+                    granted_skills.push(Ability::new(line, features_detected));
+                }
+            }
+            // ライフバーストはスキルとは別に持つ。カードのフィーチャーには LifeBurst だけを付け、
+            // 効果のフィーチャーはライフバーストの側に持たせる
+            SkillLineKind::LifeBurst => {
+                for feature in features_detected {
+                    if feature == CardFeature::LifeBurst {
+                        features.insert(feature);
+                    } else {
+                        life_burst_features.push(feature);
+                    }
+                }
+                if !line.is_empty() {
+                    life_burst_lines.push(line);
+                }
+            }
+            SkillLineKind::Own => {
+                // 注釈のみの行は除去されて空になるが、フィーチャーは残す
+                features.extend(features_detected);
+                if !line.is_empty() {  // 空の行を除去
+                    all_skills.push(line);
+                }
+            }
+        }
+    }
+
+    let life_burst = (!life_burst_lines.is_empty())
+        .then(|| Ability::new(life_burst_lines.join("\n"), life_burst_features));
+
+    if all_skills.is_empty() && granted_skills.is_empty() && life_burst.is_none() {
+        features.insert(CardFeature::Vanilla);
+    }
+
+    (Skills::from_vec(all_skills), features, granted_skills, life_burst)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SkillLineKind {
+    Own,
+    // 他のカードに与える能力(「以下の能力を得る」の枠内)
+    Granted,
+    // ライフバーストの .cardSkill の行。"LB:" は取り除いてある
+    LifeBurst,
+}
+
+#[derive(Debug, Clone)]
+struct SkillLine {
+    // 正規化してルールを適用した行
+    text: String,
+    features: Vec<CardFeature>,
+    kind: SkillLineKind,
+    // 正規化する前の行(HTMLのまま)
+    source: String,
+    // 正規化で表記が変わったか
+    normalized: bool,
+}
+
+// ライフバーストは一つの .cardSkill にまとまっているので、"LB:" の行から後は同じ .cardSkill の終わりまでライフバーストとする
+fn parse_skill_lines(source: Vec<String>) -> Vec<SkillLine> {
+    let re_br = Regex::new(r"<br\s?>").unwrap();
+    let mut lines: Vec<SkillLine> = Vec::new();
 
     for s in source {
         let new_html = wrap_by_gainskill(s);
+        let mut in_gainskill = false;
+        let mut in_life_burst = false;
+
+        for line in re_br.replace_all(&new_html, "\n").split('\n') {
+            let mut line = line.trim().to_string();
+
+            if line.starts_with("<gainskill>") {
+                in_gainskill = true;
+                line = line.replacen("<gainskill>", "", 1);
+            }
+            let block_end = line.contains("</gainskill>");
+            let source = line.replace("</gainskill>", "").trim().to_string();
+            let normalized_source = normalize_text(&source);
+            let normalized = normalized_source != source;
+
+            let (l, features) = rule_explain_to_feature(normalized_source);
+            let (text, kind) = match l.strip_prefix("LB:") {
+                Some(text) if !in_gainskill => {
+                    in_life_burst = true;
+                    (text.to_string(), SkillLineKind::LifeBurst)
+                }
+                _ if in_gainskill => (l, SkillLineKind::Granted),
+                _ if in_life_burst => (l, SkillLineKind::LifeBurst),
+                _ => (l, SkillLineKind::Own),
+            };
+            lines.push(SkillLine { text, features, kind, source, normalized });
 
-        let skills_for_this_string: Vec<String> = re_br
-            .replace_all(&new_html, "\n")
-            .split('\n')
-            .map(|line| line.trim().to_string())
-            .map(|line| {
-                let (l, features_detected) = rule_explain_to_feature(line);
-                features.extend(features_detected);
-                l
-            })
-            .filter(|line| !line.is_empty())  // 空の行を除去
-            .collect();
-
-        all_skills.extend(skills_for_this_string);
+            if block_end {
+                in_gainskill = false;
+            }
+        }
     }
 
-    (Skills::from_vec(all_skills), features)
+    lines
+}
+
+// `.cardSkill`の各行(正規化したもの)と、その行から検出されたフィーチャーを返す。
+// 他のカードに与える能力の行は、このカード自身のスキルではないので含めない
+pub fn skill_lines_from_html(text: &str) -> Vec<(String, Vec<CardFeature>)> {
+    let document: Html = Html::parse_document(text);
+    let selector_card_skill = Selector::parse(".cardSkill").unwrap();
+    let card_skills: Vec<String> = document.select(&selector_card_skill)
+        .map(|element| element.inner_html())
+        .collect();
+
+    parse_skill_lines(card_skills)
+        .into_iter()
+        .filter(|line| !line.text.is_empty() && line.kind != SkillLineKind::Granted)
+        .map(|line| (line.text, line.features))
+        .collect()
 }
 
 #[derive(Debug)]
 pub struct Token {
-    no: String,
+    no: CardNo,
     name: String,
     pronounce: String,
     artist: String,
@@ -1979,11 +2319,13 @@ pub struct Token {
     // power: OptionString,
     // user: OptionString,
     // time: Vec<String>,
-    // story: OptionString,
+    // story: Story,
     // format: Format,
     rarity: String,
     skill: Skills,
     features: HashSet<CardFeature>,
+    granted_skills: Vec<Ability>,
+    life_burst: Option<Ability>,
 }
 
 impl From<Token> for Card {
@@ -1992,6 +2334,7 @@ impl From<Token> for Card {
             no: val.no.clone(),
             name: val.name.clone(),
             pronounce: val.pronounce.clone(),
+            reading: ReadingKeys::from_pronounce(&val.pronounce),
             artist: val.artist.clone(),
             card_type: val.card_type.clone(),
             klass: OptionString::empty(),
@@ -2002,63 +2345,30 @@ impl From<Token> for Card {
             power: OptionString::empty(),
             user: OptionString::empty(),
             time: Vec::new(),
-            story: OptionString::empty(),
+            story: Story::default(),
+            is_dissona: false,
             format: Format::DivaSelection,
             rarity: val.rarity.clone(),
             skill: val.skill.clone(),
             features: val.features.clone(),
+            feature_details: FeatureDetail::from_skill_lines(&val.skill.value, &val.features),
+            granted_skills: val.granted_skills.clone(),
+            life_burst: val.life_burst.clone(),
+            original: None,
+            image: None,
         }
     }
 }
 
 impl WixossCard for Token {
-    fn from_source(source: String) -> Self {
-        let document: Html = Html::parse_document(&source);
-
-        let selector_card_num = Selector::parse(".cardNum").unwrap();
-        let card_no = match document.select(&selector_card_num).next() {
-            Some(card_no) => card_no.inner_html(),
-            None => "unknown".into()
-        };
-
-        let selector_card_name = Selector::parse(".cardName").unwrap();
-        let card_name = match document.select(&selector_card_name).next() {
-            Some(card_name) => element_to_name_and_pronounce(card_name.inner_html()),
-            None => ("unknown".into(), "unknown".into())
-        };
-
-        let selector_rarity = Selector::parse(".cardRarity").unwrap();
-        let card_rarity = match document.select(&selector_rarity).next() {
-            Some(card_rarity) => card_rarity.inner_html(),
-            None => "unknown rarity".into()
-        };
-
-        let selector_artist = Selector::parse(".cardImg p span").unwrap();
-        let artist = match document.select(&selector_artist).next() {
-            Some(artist) => artist.inner_html(),
-            None => "unknown artist".into()
-        };
-
-        let selector_card_data = Selector::parse(".cardData dd").unwrap();
-
-        let mut card_data: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_data) {
-            card_data.push(element.inner_html());
-        }
-
-        let selector_card_skill = Selector::parse(".cardSkill").unwrap();
-        let mut card_skills: Vec<String> = Vec::new();
-        for element in document.select(&selector_card_skill) {
-            card_skills.push(element.inner_html());
-        }
-
-        let (skill, features) = parse_card_skill(card_skills.clone());
+    fn from_page(page: &CardPage) -> Self {
+        let card_data = &page.data;
 
         Self {
-            no: card_no,
-            name: card_name.0,
-            pronounce: card_name.1,
-            artist,
+            no: page.no.clone(),
+            name: page.name.clone(),
+            pronounce: page.pronounce.clone(),
+            artist: page.artist.clone(),
             card_type: CardType::Token,
             // klass: OptionString::from_string(card_data[1].clone()),
             color: card_data[2].clone(),
@@ -2069,9 +2379,11 @@ impl WixossCard for Token {
             // time: split_by_break(card_data[9].clone()),
             // story: parse_story(card_data[11].clone().trim().to_string()),
             // format: parse_format(card_data[10].clone()),
-            rarity: card_rarity,
-            skill,
-            features,
+            rarity: page.rarity.clone(),
+            skill: page.skill.clone(),
+            features: page.features.clone(),
+            granted_skills: page.granted_skills.clone(),
+            life_burst: page.life_burst.clone(),
         }
     }
 }
@@ -2095,6 +2407,9 @@ impl Display for Token {
         // write!(f, "フォーマット\t:{}\n", self.format)?;
         writeln!(f, "レアリティ\t:{}", self.rarity)?;
         writeln!(f, "テキスト({})\t:{}", self.skill.value.len(), self.skill)?;
+        if let Some(life_burst) = &self.life_burst {
+            writeln!(f, "ライフバースト\t:{}", life_burst)?;
+        }
         writeln!(f, "フィーチャー({})\t:{:?}", self.features.len(), self.features.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "))?;
         write!(f, "")
     }
@@ -2118,19 +2433,19 @@ fn rule_explain_to_feature(text: String) -> (String, Vec<CardFeature>) {
         (r"『", true, "", features![]),  // アクセのみ？
         (r"』", true, "", features![]),  // アクセのみ？
         (r"ライフバースト：", true, "LB:", features![CardFeature::LifeBurst]),
-        (r"（対戦相手のライフクロスが１枚以上ある場合、ライフクロス１枚をクラッシュし、０枚の場合、あなたはゲームに勝利する）", true, "", features![CardFeature::Damage]),
-        (r"（【ランサー】を持つシグニがバトルでシグニをバニッシュしたとき、対戦相手のライフクロスを１枚クラッシュする）", true, "", features![CardFeature::Lancer]),
+        (r"（対戦相手のライフクロスが1枚以上ある場合、ライフクロス1枚をクラッシュし、0枚の場合、あなたはゲームに勝利する）", true, "", features![CardFeature::Damage]),
+        (r"（【ランサー】を持つシグニがバトルでシグニをバニッシュしたとき、対戦相手のライフクロスを1枚クラッシュする）", true, "", features![CardFeature::Lancer]),
         (r"（このクラフトは効果以外によっては場に出せない）", true, "", features![CardFeature::Craft]),
         (r"（このスペルはあなたのメインフェイズにルリグデッキから使用できる）", true, "", features![CardFeature::Craft]),
         (r"（クラフトであるスペルは、使用後にゲームから除外される）", true, "", features![CardFeature::Craft]),
         (r"アクセ", false, "*ACCE*", features![CardFeature::Acce]),
-        (r"（【アクセ】はシグニ１体に１枚までしか付けられない。このクラフトが付いているシグニが場を離れるとこのクラフトはゲームから除外される）", true, "", features![CardFeature::Acce]),
-        (r"（あなたのルリグの下からカードを合計４枚ルリグトラッシュに置く）", true, "*EXCEED*", features![CardFeature::Exceed]),
-        (r"（【チーム】または【ドリームチーム】を持つピースはルリグデッキに合計１枚までしか入れられない）", true, "*DREAM TEAM*", features![]),
-        (r"（あなたの場にいるルリグ３体がこの条件を満たす）", true, "*TEAM*", features![]),
+        (r"（【アクセ】はシグニ1体に1枚までしか付けられない。このクラフトが付いているシグニが場を離れるとこのクラフトはゲームから除外される）", true, "", features![CardFeature::Acce]),
+        (r"（あなたのルリグの下からカードを合計4枚ルリグトラッシュに置く）", true, "*EXCEED*", features![CardFeature::Exceed]),
+        (r"（【チーム】または【ドリームチーム】を持つピースはルリグデッキに合計1枚までしか入れられない）", true, "*DREAM TEAM*", features![]),
+        (r"（あなたの場にいるルリグ3体がこの条件を満たす）", true, "*TEAM*", features![]),
         (r"（シグニは覚醒すると場にあるかぎり覚醒状態になる）", true, "*AWAKE*", features![CardFeature::Awake]),
         (r"（凍結されたシグニは次の自分のアップフェイズにアップしない）", true, "*FROZEN*", features![CardFeature::Freeze]),
-        (r"（フェゾーネマジックは５種類ある）", true, "*FESONE MAGIC*", features![]),
+        (r"（フェゾーネマジックは5種類ある）", true, "*FESONE MAGIC*", features![]),
         (r"（【出】能力の：の左側はコストである。コストを支払わず発動しないことを選んでもよい）", true, "*CIP COST*", features![]),
         (r"ガードアイコン", true, "ガード", features![CardFeature::Guard]),
         (r"捨てさせる。", false, "*HAND DESTRUCTION*", features![CardFeature::DiscardOpponent]),
@@ -2149,28 +2464,28 @@ fn rule_explain_to_feature(text: String) -> (String, Vec<CardFeature>) {
         (r"ダブルクラッシュ", false, "*DOUBLE CRUSH*", features![CardFeature::DoubleCrush]),
         (r"トリプルクラッシュ", false, "*TRIPLE CRUSH*", features![CardFeature::TripleCrush]),
         (r"Sランサー", false, "*S LANCER*", features![CardFeature::SLancer]),
-        (r"Ｓランサー", false, "*S LANCER*", features![CardFeature::SLancer]),
-        (r"バニッシュ", false, "*BANISH*", features![CardFeature::Banish]),
+        // 「バニッシュされない」(Invulnerable)、「バニッシュされたとき」(OnBanish)は除去ではない
+        (r"バニッシュ(する|し[、て])", false, "*BANISH*", features![CardFeature::Banish]),
         (r"凍結する", false, "*FREEZE*", features![CardFeature::Freeze]),
-        (r"対戦相手のシグニを[（\u{FF10}-\u{FF19}）]+体まで対象とし、それらを手札に戻", false, "*BOUNCE*", features![CardFeature::Bounce]),
-        (r"対戦相手のシグニ[（\u{FF10}-\u{FF19}）]+体を対象とし、それを手札に戻", false, "BOUNCE", features![CardFeature::Bounce]),
+        (r"対戦相手のシグニを\d+体まで対象とし、それらを手札に戻", false, "*BOUNCE*", features![CardFeature::Bounce]),
+        (r"対戦相手のシグニ\d+体を対象とし、それを手札に戻", false, "BOUNCE", features![CardFeature::Bounce]),
         // (r"手札に加え", false, "*SALVAGE*", features![CardFeature::Salvage]),
-        (r"ライフクロス[（\u{FF10}-\u{FF19}）]+枚をトラッシュに置", false, "*LIFE TRASH*", features![CardFeature::LifeTrash]),
-        (r"エナゾーンからカード[（\u{FF10}-\u{FF19}）]+枚を.+トラッシュに置", false, "*ENER ATTACK*", features![CardFeature::EnerAttack]),
+        (r"ライフクロス\d+枚をトラッシュに置", false, "*LIFE TRASH*", features![CardFeature::LifeTrash]),
+        (r"エナゾーンからカード\d+枚を.+トラッシュに置", false, "*ENER ATTACK*", features![CardFeature::EnerAttack]),
         (r"ルリグトラッシュに置", false, "*LRIG TRASH*", features![CardFeature::LrigTrash]),
         // (r"アタックフェイズ開始時", false, "*ON ATTACK START*", features![CardFeature::OnAttackStart]),
         (r"ライフクロスに加える", false, "*ADD LIFE*", features![CardFeature::AddLife]),
         (r"ランサー", false, "*LANCER*", features![CardFeature::Lancer]),
-        (r"ライフクロスを１枚クラッシュする", false, "*CRUSH*", features![CardFeature::LifeCrush]),
-        (r"対戦相手のライフクロス１枚をクラッシュする。", false, "*CRUSH*", features![CardFeature::LifeCrush]),
+        (r"ライフクロスを1枚クラッシュする", false, "*CRUSH*", features![CardFeature::LifeCrush]),
+        (r"対戦相手のライフクロス1枚をクラッシュする。", false, "*CRUSH*", features![CardFeature::LifeCrush]),
         (r"対戦相手にダメージを与える。", false, "*DAMAGE*", features![CardFeature::Damage]),
         (r"リコレクトアイコン", false, "*RECOLLECT*", features![CardFeature::Recollect]),
         (r"枚見", false, "*SEEK*", features![CardFeature::SeekTop]),
         (r"能力を失う", false, "*ERASE SKILL*", features![CardFeature::EraseSkill]),
         (r"アタックできない", false, "*NON ATTACKABLE*", features![CardFeature::NonAttackable]),
-        (r"カードを[（\u{FF10}-\u{FF19}）]+枚引", false, "*DRAW*", features![CardFeature::Draw]),
-        (r"デッキの上からカードを[（\u{FF10}-\u{FF19}）]+枚トラッシュに置", false, "*DROP*", features![CardFeature::Drop]),
-        (r"対戦相手のエナゾーンからカードを[（\u{FF10}-\u{FF19}）]+枚まで対象とし、それらを手札に戻", false, "*ENER ATTACK*", features![CardFeature::EnerAttack]),
+        (r"カードを\d+枚引", false, "*DRAW*", features![CardFeature::Draw]),
+        (r"デッキの上からカードを\d+枚トラッシュに置", false, "*DROP*", features![CardFeature::Drop]),
+        (r"対戦相手のエナゾーンからカードを\d+枚まで対象とし、それらを手札に戻", false, "*ENER ATTACK*", features![CardFeature::EnerAttack]),
         (r"デッキの一番下に置", false, "*DECK BOUNCE*", features![CardFeature::DeckBounce]),
         (r"シグニのパワーを＋", false, "*POWER UP*", features![CardFeature::PowerUp]),
         (r"(シグニ|それ)のパワーを－", false, "*POWER DOWN*", features![CardFeature::PowerDown]),
@@ -2178,9 +2493,25 @@ fn rule_explain_to_feature(text: String) -> (String, Vec<CardFeature>) {
         (r"トラッシュからシグニ.+場に出", false, "*REANIMATE*", features![CardFeature::Reanimate]),
         (r"このルリグをアップし", false, "*ADDITIONAL ATTACK*", features![CardFeature::AdditionalAttack]),
         (r"対戦相手は【ガード】ができない", false, "*UNGUARDABLE*", features![CardFeature::UnGuardable]),
-        (r"スペル[（\u{FF10}-\u{FF19}）]+枚を.+手札に加え", false, "*SALVAGE SPELL*", features![CardFeature::SalvageSpell]),
-        (r"シグニ[（\u{FF10}-\u{FF19}）]+枚を.+手札に加え", false, "*SALVAGE SIGNI*", features![CardFeature::Salvage]),
+        (r"スペル\d+枚を.+手札に加え", false, "*SALVAGE SPELL*", features![CardFeature::SalvageSpell]),
+        (r"シグニ\d+枚を.+手札に加え", false, "*SALVAGE SIGNI*", features![CardFeature::Salvage]),
         (r"このシグニがアタックしたとき.+バニッシュする", false, "*BANISH ON ATTACK*", features![CardFeature::BanishOnAttack]),
+        (r"デッキからトラッシュに置かれたとき", false, "*ON DROP*", features![CardFeature::OnDrop]),
+        (r"リフレッシュしたとき", false, "*ON REFRESH*", features![CardFeature::OnRefresh]),
+        (r"スペルを使用したとき", false, "*ON SPELL*", features![CardFeature::OnSpell]),
+        (r"アーツを使用したとき", false, "*ON ARTS*", features![CardFeature::OnArts]),
+        (r"ピースを使用したとき", false, "*ON PIECE*", features![CardFeature::OnPiece]),
+        (r"バニッシュ(され|し)たとき", false, "*ON BANISH*", features![CardFeature::OnBanish]),
+        (r"【ガード】したとき", false, "*ON GUARD*", features![CardFeature::OnGuard]),
+        (r"能力か効果の対象になったとき", false, "*ON TOUCH*", features![CardFeature::OnTouch]),
+        (r"エクシードのコストを支払ったとき", false, "*ON EXCEED*", features![CardFeature::OnExceed]),
+        (r"ライフバースト(能力)?が発動したとき", false, "*ON BURST*", features![CardFeature::OnBurst]),
+        (r"ライフクロス.*クラッシュされたとき", false, "*ON CRUSH*", features![CardFeature::OnLifeCrush]),
+        (r"シグニゾーンに(移動|配置)", false, "*POSITION*", features![CardFeature::Position]),
+        (r"効果を受けない", false, "*UNTOUCHABLE*", features![CardFeature::Untouchable]),
+        (r"バニッシュされない", false, "*INVULNERABLE*", features![CardFeature::Invulnerable]),
+        (r"デッキの一番上を(見|公開)", false, "*TOP CHECK*", features![CardFeature::TopCheck]),
+        (r"【ライズ】", false, "*RISE*", features![CardFeature::Rise]),
 
     ];
 
@@ -2203,20 +2534,32 @@ fn rule_explain_to_feature(text: String) -> (String, Vec<CardFeature>) {
 
 
 fn replace_img_with_alt(html: String) -> String {
-    let re = Regex::new(r#"<img[^>]*alt="([^"]*)"[^>]*>"#).unwrap();
-    let replaced = re.replace_all(&html, |caps: &regex::Captures| {
-        let alt_text = &caps[1];
-        alt_text.replace("2》", "》")
-    });
-    replaced.into_owned()
+    SkillRenderer::new(RenderMode::Plain).render(&html)
 }
 
-fn parse_story(html: String) -> OptionString {
-    if html.contains(r#"class="cardData_story_img""#) {
-        OptionString::from_string("ディソナ".into())
-    } else {
-        OptionString::empty()
+fn parse_story(html: String) -> Story {
+    let re_img = Regex::new(r#"<img[^>]*src="([^"]*)"[^>]*>"#).unwrap();
+    let re_tag = Regex::new(r"<[^>]*>").unwrap();
+
+    let mut tags: Vec<StoryTag> = Vec::new();
+    for caps in re_img.captures_iter(&html) {
+        let file = caps[1].rsplit('/').next().unwrap_or("").to_string();
+        let tag = match file.as_str() {
+            "icon_txt_dissona.png" => StoryTag::Dissona,
+            _ => StoryTag::Icon(file.trim_start_matches("icon_txt_").trim_end_matches(".png").to_string()),
+        };
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    // アイコン以外に書かれた文字列。"-"は未設定
+    let text = re_tag.replace_all(&html, "").trim().to_string();
+    if !text.is_empty() && text != "-" {
+        tags.push(StoryTag::Text(text));
     }
+
+    Story { tags }
 }
 
 fn split_by_break(html: String) -> Vec<String> {
@@ -0,0 +1,26 @@
+use unicode_normalization::UnicodeNormalization;
+
+// 全角英数字(Ｓランサー、１枚)と半角カナをNFKCで揃える。
+// （）：＋－などの全角記号はカードテキスト本来の表記なので残し、ルールのパターンもそちらで書く
+pub fn normalize_text(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    let mut run = String::new();
+
+    for c in text.chars() {
+        if is_normalize_target(c) {
+            run.push(c);
+            continue;
+        }
+        // 半角カナの濁点は直前の文字と合成させるため、連続した部分をまとめて変換する
+        normalized.extend(run.nfkc());
+        run.clear();
+        normalized.push(c);
+    }
+    normalized.extend(run.nfkc());
+
+    normalized
+}
+
+fn is_normalize_target(c: char) -> bool {
+    matches!(c, '０'..='９' | 'Ａ'..='Ｚ' | 'ａ'..='ｚ' | '\u{FF61}'..='\u{FF9F}')
+}
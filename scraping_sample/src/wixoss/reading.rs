@@ -0,0 +1,187 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+// カードの読み(カタカナ)から作る検索用のキー
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ReadingKeys {
+    pub hiragana: String,
+    pub katakana: String,
+    // ヘボン式。長音は母音を重ねる(コード -> koodo)
+    pub romaji: String,
+    // 五十音順に並べるためのキー。濁点・半濁点と小書きを外したひらがなで比べ、同じなら元のひらがなで比べる。
+    // 二つは半角空白で区切る(読みに使う文字より小さいので、「はな」が「はなび」より前になる)
+    pub collation: String,
+}
+
+impl ReadingKeys {
+    pub fn from_pronounce(pronounce: &str) -> Self {
+        let katakana = to_katakana(pronounce);
+        let hiragana = to_hiragana(&katakana);
+        let romaji = to_romaji(&hiragana);
+        let collation = format!("{} {}", to_seion(&hiragana), hiragana);
+
+        Self { hiragana, katakana, romaji, collation }
+    }
+
+    // ひらがな・カタカナ・ローマ字のいずれかによる前方一致
+    pub fn starts_with(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return true;
+        }
+        self.hiragana.starts_with(&to_hiragana(&query)) || self.romaji.starts_with(&query)
+    }
+}
+
+pub fn to_hiragana(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'ァ'..='ヶ' => char::from_u32(c as u32 - 0x60).unwrap(),
+            _ => c,
+        })
+        .collect()
+}
+
+pub fn to_katakana(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'ぁ'..='ゖ' => char::from_u32(c as u32 + 0x60).unwrap(),
+            _ => c,
+        })
+        .collect()
+}
+
+// 濁点・半濁点を外し、小書きを通常の大きさに、長音を直前の母音にする
+fn to_seion(hiragana: &str) -> String {
+    let mut seion = String::new();
+    let mut last_vowel: Option<char> = None;
+
+    for c in hiragana.chars() {
+        let base = match c {
+            'ー' => match last_vowel {
+                Some(v) => v,
+                None => c,
+            },
+            'ゔ' => 'う',
+            _ => SEION.iter().find(|(from, _)| from.contains(c)).map(|(_, to)| *to).unwrap_or(c),
+        };
+        last_vowel = vowel_of(base).or(last_vowel);
+        seion.push(base);
+    }
+    seion
+}
+
+const SEION: [(&str, char); 29] = [
+    ("が", 'か'), ("ぎ", 'き'), ("ぐ", 'く'), ("げ", 'け'), ("ご", 'こ'),
+    ("ざ", 'さ'), ("じ", 'し'), ("ず", 'す'), ("ぜ", 'せ'), ("ぞ", 'そ'),
+    ("だ", 'た'), ("ぢ", 'ち'), ("づっ", 'つ'), ("で", 'て'), ("ど", 'と'),
+    ("ばぱ", 'は'), ("びぴ", 'ひ'), ("ぶぷ", 'ふ'), ("べぺ", 'へ'), ("ぼぽ", 'ほ'),
+    ("ゃ", 'や'), ("ゅ", 'ゆ'), ("ょ", 'よ'), ("ゎ", 'わ'),
+    ("ぁ", 'あ'), ("ぃ", 'い'), ("ぅ", 'う'), ("ぇ", 'え'), ("ぉ", 'お'),
+];
+
+fn vowel_of(c: char) -> Option<char> {
+    romaji_of(c).and_then(|r| r.chars().last()).and_then(|v| match v {
+        'a' => Some('あ'),
+        'i' => Some('い'),
+        'u' => Some('う'),
+        'e' => Some('え'),
+        'o' => Some('お'),
+        _ => None,
+    })
+}
+
+pub fn to_romaji(text: &str) -> String {
+    let hiragana: Vec<char> = to_hiragana(text).chars().collect();
+    let mut romaji = String::new();
+    let mut i = 0;
+
+    while i < hiragana.len() {
+        let c = hiragana[i];
+
+        // 拗音など、小書きと組み合わせる二文字
+        if let Some(&next) = hiragana.get(i + 1) {
+            if let Some(r) = digraph_of(c, next) {
+                romaji.push_str(r);
+                i += 2;
+                continue;
+            }
+        }
+
+        match c {
+            'っ' => {
+                // 次の子音を重ねる。ch の前だけは t
+                let next = hiragana.get(i + 1).and_then(|&n| {
+                    hiragana.get(i + 2).and_then(|&nn| digraph_of(n, nn)).or(romaji_of(n))
+                });
+                match next {
+                    Some(r) if r.starts_with("ch") => romaji.push('t'),
+                    Some(r) if !r.starts_with(['a', 'i', 'u', 'e', 'o']) => romaji.push(r.chars().next().unwrap()),
+                    _ => romaji.push_str("xtsu"),
+                }
+            }
+            'ー' => {
+                let last = romaji.chars().last().filter(|v| "aiueo".contains(*v));
+                if let Some(v) = last {
+                    romaji.push(v);
+                }
+            }
+            _ => match romaji_of(c) {
+                Some(r) => romaji.push_str(r),
+                None => romaji.extend(c.to_lowercase()),
+            },
+        }
+        i += 1;
+    }
+
+    romaji
+}
+
+fn digraph_of(c: char, small: char) -> Option<&'static str> {
+    let r = match (c, small) {
+        ('き', 'ゃ') => "kya", ('き', 'ゅ') => "kyu", ('き', 'ょ') => "kyo",
+        ('ぎ', 'ゃ') => "gya", ('ぎ', 'ゅ') => "gyu", ('ぎ', 'ょ') => "gyo",
+        ('し', 'ゃ') => "sha", ('し', 'ゅ') => "shu", ('し', 'ょ') => "sho", ('し', 'ぇ') => "she",
+        ('じ', 'ゃ') => "ja", ('じ', 'ゅ') => "ju", ('じ', 'ょ') => "jo", ('じ', 'ぇ') => "je",
+        ('ち', 'ゃ') => "cha", ('ち', 'ゅ') => "chu", ('ち', 'ょ') => "cho", ('ち', 'ぇ') => "che",
+        ('に', 'ゃ') => "nya", ('に', 'ゅ') => "nyu", ('に', 'ょ') => "nyo",
+        ('ひ', 'ゃ') => "hya", ('ひ', 'ゅ') => "hyu", ('ひ', 'ょ') => "hyo",
+        ('び', 'ゃ') => "bya", ('び', 'ゅ') => "byu", ('び', 'ょ') => "byo",
+        ('ぴ', 'ゃ') => "pya", ('ぴ', 'ゅ') => "pyu", ('ぴ', 'ょ') => "pyo",
+        ('み', 'ゃ') => "mya", ('み', 'ゅ') => "myu", ('み', 'ょ') => "myo",
+        ('り', 'ゃ') => "rya", ('り', 'ゅ') => "ryu", ('り', 'ょ') => "ryo",
+        ('て', 'ぃ') => "ti", ('で', 'ぃ') => "di", ('と', 'ぅ') => "tu", ('ど', 'ぅ') => "du",
+        ('で', 'ゅ') => "dyu",
+        ('ふ', 'ぁ') => "fa", ('ふ', 'ぃ') => "fi", ('ふ', 'ぇ') => "fe", ('ふ', 'ぉ') => "fo",
+        ('う', 'ぃ') => "wi", ('う', 'ぇ') => "we", ('う', 'ぉ') => "wo",
+        ('ゔ', 'ぁ') => "va", ('ゔ', 'ぃ') => "vi", ('ゔ', 'ぇ') => "ve", ('ゔ', 'ぉ') => "vo",
+        ('つ', 'ぁ') => "tsa", ('つ', 'ぃ') => "tsi", ('つ', 'ぇ') => "tse", ('つ', 'ぉ') => "tso",
+        _ => return None,
+    };
+    Some(r)
+}
+
+fn romaji_of(c: char) -> Option<&'static str> {
+    let r = match c {
+        'あ' => "a", 'い' => "i", 'う' => "u", 'え' => "e", 'お' => "o",
+        'か' => "ka", 'き' => "ki", 'く' => "ku", 'け' => "ke", 'こ' => "ko",
+        'が' => "ga", 'ぎ' => "gi", 'ぐ' => "gu", 'げ' => "ge", 'ご' => "go",
+        'さ' => "sa", 'し' => "shi", 'す' => "su", 'せ' => "se", 'そ' => "so",
+        'ざ' => "za", 'じ' => "ji", 'ず' => "zu", 'ぜ' => "ze", 'ぞ' => "zo",
+        'た' => "ta", 'ち' => "chi", 'つ' => "tsu", 'て' => "te", 'と' => "to",
+        'だ' => "da", 'ぢ' => "ji", 'づ' => "zu", 'で' => "de", 'ど' => "do",
+        'な' => "na", 'に' => "ni", 'ぬ' => "nu", 'ね' => "ne", 'の' => "no",
+        'は' => "ha", 'ひ' => "hi", 'ふ' => "fu", 'へ' => "he", 'ほ' => "ho",
+        'ば' => "ba", 'び' => "bi", 'ぶ' => "bu", 'べ' => "be", 'ぼ' => "bo",
+        'ぱ' => "pa", 'ぴ' => "pi", 'ぷ' => "pu", 'ぺ' => "pe", 'ぽ' => "po",
+        'ま' => "ma", 'み' => "mi", 'む' => "mu", 'め' => "me", 'も' => "mo",
+        'や' => "ya", 'ゆ' => "yu", 'よ' => "yo",
+        'ら' => "ra", 'り' => "ri", 'る' => "ru", 'れ' => "re", 'ろ' => "ro",
+        'わ' => "wa", 'ゐ' => "i", 'ゑ' => "e", 'を' => "o", 'ん' => "n",
+        'ゔ' => "vu",
+        'ぁ' => "a", 'ぃ' => "i", 'ぅ' => "u", 'ぇ' => "e", 'ぉ' => "o",
+        'ゃ' => "ya", 'ゅ' => "yu", 'ょ' => "yo", 'ゎ' => "wa",
+        _ => return None,
+    };
+    Some(r)
+}
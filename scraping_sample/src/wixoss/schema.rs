@@ -0,0 +1,29 @@
+use schemars::schema_for;
+use serde_json::Value;
+use crate::wixoss::Card;
+
+// Cardを書き出したJSONのスキーマのバージョン。
+// 既存のフィールドの削除・型の変更などで、以前の書き出しを読む側が壊れる場合はメジャーを上げる。
+// フィールドの追加だけならマイナーを上げる
+pub const CARD_SCHEMA_VERSION: &str = "1.3.0";
+
+pub fn card_schema_file_name() -> String {
+    let major = CARD_SCHEMA_VERSION.split('.').next().unwrap();
+    format!("card.v{}.schema.json", major)
+}
+
+pub fn card_json_schema() -> Value {
+    let mut schema = serde_json::to_value(schema_for!(Card)).unwrap();
+
+    let root = schema.as_object_mut().unwrap();
+    root.insert("$id".into(), Value::String(card_schema_file_name()));
+    root.insert("version".into(), Value::String(CARD_SCHEMA_VERSION.into()));
+
+    schema
+}
+
+pub fn card_json_schema_string() -> String {
+    let mut json = serde_json::to_string_pretty(&card_json_schema()).unwrap();
+    json.push('\n');
+    json
+}
@@ -0,0 +1,154 @@
+use regex::Regex;
+use serde::Serialize;
+
+// カードテキスト中のアイコン画像(<img alt="《赤》">など)を扱う。
+// フィーチャー判定用のプレーンテキストと、UI・エクスポート用のマークアップを同じトークン列から作る
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum IconKind {
+    Color,
+    Timing,
+    OncePerTurn,
+    Guard,
+    LifeBurst,
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Icon {
+    pub kind: IconKind,
+    // 表示用の文字列。《白2》のような大きさ違いのアイコンも《白》に揃える
+    pub label: String,
+    // アイコン画像のファイル名(icon_txt_red.pngなど)。srcが無い場合は空
+    pub file: String,
+}
+
+impl Icon {
+    fn from_alt(alt: &str, src: &str) -> Self {
+        let label = alt.replace("2》", "》");
+
+        let kind = match label.as_str() {
+            "《白》" | "《赤》" | "《青》" | "《緑》" | "《黒》" | "《無》" => IconKind::Color,
+            "【自】" | "【起】" | "【出】" | "【常】" => IconKind::Timing,
+            _ if label.starts_with("《ターン") => IconKind::OncePerTurn,
+            "《ガードアイコン》" => IconKind::Guard,
+            "ライフバースト" => IconKind::LifeBurst,
+            _ => IconKind::Other,
+        };
+
+        let file = src.rsplit('/').next().unwrap_or("").to_string();
+
+        Self { kind, label, file }
+    }
+
+    fn css_class(&self) -> &'static str {
+        match self.kind {
+            IconKind::Color => "icon-color",
+            IconKind::Timing => "icon-timing",
+            IconKind::OncePerTurn => "icon-once-per-turn",
+            IconKind::Guard => "icon-guard",
+            IconKind::LifeBurst => "icon-life-burst",
+            IconKind::Other => "icon-other",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", content = "value")]
+pub enum SkillToken {
+    Text(String),
+    Icon(Icon),
+    LineBreak,
+}
+
+pub fn tokenize(html: &str) -> Vec<SkillToken> {
+    let re = Regex::new(r#"<img[^>]*>|<br\s*/?>"#).unwrap();
+    let re_alt = Regex::new(r#"alt="([^"]*)""#).unwrap();
+    let re_src = Regex::new(r#"src="([^"]*)""#).unwrap();
+
+    let mut tokens: Vec<SkillToken> = Vec::new();
+    let mut last = 0;
+
+    for m in re.find_iter(html) {
+        if m.start() > last {
+            tokens.push(SkillToken::Text(html[last..m.start()].to_string()));
+        }
+        last = m.end();
+
+        let tag = m.as_str();
+        if tag.starts_with("<br") {
+            tokens.push(SkillToken::LineBreak);
+            continue;
+        }
+
+        // altの無い画像(ストーリーアイコンなど)はテキストとしては何も出さない
+        if let Some(alt) = re_alt.captures(tag) {
+            let src = re_src.captures(tag).map(|c| c[1].to_string()).unwrap_or_default();
+            tokens.push(SkillToken::Icon(Icon::from_alt(&alt[1], &src)));
+        }
+    }
+    if last < html.len() {
+        tokens.push(SkillToken::Text(html[last..].to_string()));
+    }
+
+    tokens
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderMode {
+    // アイコンを《赤》のような文字列に置き換える
+    Plain,
+    // SkillTokenの列をJSONで出す
+    Tokens,
+    // アイコンをローカルの画像への参照にする
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Clone)]
+pub struct SkillRenderer {
+    mode: RenderMode,
+    icon_dir: String,
+}
+
+impl SkillRenderer {
+    pub fn new(mode: RenderMode) -> Self {
+        Self { mode, icon_dir: "icons".into() }
+    }
+
+    // Markdown/HTMLでアイコン画像を参照するディレクトリ
+    pub fn icon_dir(mut self, icon_dir: &str) -> Self {
+        self.icon_dir = icon_dir.trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn render(&self, html: &str) -> String {
+        self.render_tokens(&tokenize(html))
+    }
+
+    pub fn render_tokens(&self, tokens: &[SkillToken]) -> String {
+        match self.mode {
+            RenderMode::Plain => tokens.iter().map(|t| match t {
+                SkillToken::Text(text) => text.clone(),
+                SkillToken::Icon(icon) => icon.label.clone(),
+                SkillToken::LineBreak => "\n".into(),
+            }).collect(),
+            RenderMode::Tokens => serde_json::to_string(tokens).unwrap(),
+            RenderMode::Markdown => tokens.iter().map(|t| match t {
+                SkillToken::Text(text) => text.clone(),
+                SkillToken::Icon(icon) if icon.file.is_empty() => icon.label.clone(),
+                SkillToken::Icon(icon) => format!("![{}]({}/{})", icon.label, self.icon_dir, icon.file),
+                SkillToken::LineBreak => "  \n".into(),
+            }).collect(),
+            RenderMode::Html => tokens.iter().map(|t| match t {
+                SkillToken::Text(text) => text.clone(),
+                SkillToken::Icon(icon) if icon.file.is_empty() => icon.label.clone(),
+                SkillToken::Icon(icon) => format!(
+                    r#"<img class="icon {}" src="{}/{}" alt="{}">"#,
+                    icon.css_class(), self.icon_dir, icon.file, icon.label
+                ),
+                SkillToken::LineBreak => "<br>".into(),
+            }).collect(),
+        }
+    }
+}
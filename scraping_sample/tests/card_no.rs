@@ -0,0 +1,90 @@
+use scraping_sample::wixoss::card_no::{CardNo, CardNoError};
+use scraping_sample::wixoss::deck::DeckList;
+
+fn no(s: &str) -> CardNo {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parse_card_numbers() {
+    let card = no("WXDi-P12-TK01A");
+    assert_eq!(card.set(), "WXDi");
+    assert_eq!(card.sub_code(), Some("P12"));
+    assert_eq!(card.number(), 1);
+    assert_eq!(card.variant(), Some("A"));
+    assert_eq!(card.product_code(), "WXDi-P12");
+    // トークンの表裏は別のカード
+    assert_eq!(card.base().as_str(), "WXDi-P12-TK01A");
+
+    let promo = no("PR-K060");
+    assert_eq!(promo.set(), "PR");
+    assert_eq!(promo.sub_code(), None);
+    assert_eq!(promo.number(), 60);
+    assert_eq!(promo.variant(), None);
+
+    let coin = no("WDA-F01-CO-01");
+    assert_eq!(coin.sub_code(), Some("F01-CO"));
+    assert_eq!(coin.relative_cache_path(), "WDA-F01-CO/01.html");
+
+    // パラレル
+    let parallel = no("WXDi-P14-001P");
+    assert_eq!(parallel.variant(), Some("P"));
+    assert_eq!(parallel.base(), no("WXDi-P14-001"));
+}
+
+#[test]
+fn cache_layout_is_unchanged() {
+    assert_eq!(no("WXDi-P12-071").relative_cache_path(), "WXDi-P12/071.html");
+    assert_eq!(no("WX22-025").relative_cache_path(), "WX22/025.html");
+    assert_eq!(no("WXK03-TK-01B").relative_cache_path(), "WXK03-TK/01B.html");
+}
+
+#[test]
+fn invalid_card_numbers() {
+    assert_eq!("".parse::<CardNo>(), Err(CardNoError::Empty));
+    assert!(matches!("WXDi".parse::<CardNo>(), Err(CardNoError::MissingNumber(_))));
+    assert!(matches!("WXDi--001".parse::<CardNo>(), Err(CardNoError::MissingNumber(_))));
+    assert!(matches!("WXDi-P12-TK".parse::<CardNo>(), Err(CardNoError::InvalidNumber(_))));
+    assert!(matches!("WXDi-P12/071".parse::<CardNo>(), Err(CardNoError::InvalidCharacter(_))));
+}
+
+#[test]
+fn card_numbers_sort_by_number() {
+    let mut cards = [no("WXDi-P12-100"), no("WXDi-P12-TK01A"), no("WXDi-P12-009"), no("WXDi-P12-009P"), no("WX22-025")];
+    cards.sort();
+    let sorted: Vec<&str> = cards.iter().map(|c| c.as_str()).collect();
+    assert_eq!(sorted, ["WX22-025", "WXDi-P12-009", "WXDi-P12-009P", "WXDi-P12-100", "WXDi-P12-TK01A"]);
+
+    // 弾の番号も数として比べる
+    let mut cards = [no("WXDi-P10-001"), no("WXDi-P9-001"), no("WXDi-D09-001"), no("WX10-001"), no("WX9-001"), no("WXDi-CP01-001")];
+    cards.sort();
+    let sorted: Vec<&str> = cards.iter().map(|c| c.as_str()).collect();
+    assert_eq!(sorted, ["WX9-001", "WX10-001", "WXDi-CP01-001", "WXDi-D09-001", "WXDi-P9-001", "WXDi-P10-001"]);
+}
+
+#[test]
+fn serialize_as_string() {
+    let json = serde_json::to_string(&no("WXDi-P14-001")).unwrap();
+    assert_eq!(json, r#""WXDi-P14-001""#);
+    assert_eq!(serde_json::from_str::<CardNo>(&json).unwrap(), no("WXDi-P14-001"));
+    assert!(serde_json::from_str::<CardNo>(r#""WXDi""#).is_err());
+}
+
+#[test]
+fn deck_list() {
+    let deck: DeckList = "
+        # main deck
+        WXDi-P14-006 x2
+        3 WXDi-P12-071
+        WXDi-P14-001
+        WXDi-P14-006 ×1
+    ".parse().unwrap();
+
+    assert_eq!(deck.total(), 7);
+    assert_eq!(deck.count_of(&no("WXDi-P14-006")), 3);
+    assert_eq!(deck.to_string(), "WXDi-P12-071 x3\nWXDi-P14-001 x1\nWXDi-P14-006 x3\n");
+
+    let error = "WXDi-P14-001\nWXDi x2".parse::<DeckList>().unwrap_err();
+    assert!(error.to_string().starts_with("line 2:"), "{}", error);
+    assert!("WXDi-P14-001 x0".parse::<DeckList>().is_err());
+}
@@ -0,0 +1,76 @@
+// Cardの書き出し形式のJSONスキーマ(schema/card.v*.schema.json)のテスト。
+// スキーマを変更した場合は CARD_SCHEMA_VERSION を上げて
+//     cargo run -p scraping_sample --example export_card_schema
+// で再生成すること。
+
+use scraping_sample::wixoss::schema::{card_json_schema, card_json_schema_string, card_schema_file_name};
+use jsonschema::Validator;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn snapshot_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = Vec::new();
+
+    for entry in fs::read_dir(dir).expect("fixture dir read error") {
+        let path: PathBuf = entry.unwrap().path();
+        if path.is_dir() {
+            files.extend(snapshot_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    files
+}
+
+fn validation_errors(validator: &Validator, card: &Value) -> Vec<String> {
+    validator.iter_errors(card)
+        .map(|e| format!("{}: {}", e.instance_path, e))
+        .collect()
+}
+
+#[test]
+fn published_schema_is_up_to_date() {
+    let path = Path::new("schema").join(card_schema_file_name());
+    let published = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("{} is missing (run the export_card_schema example)", path.display()));
+
+    assert!(
+        published == card_json_schema_string(),
+        "{} is out of date. bump CARD_SCHEMA_VERSION if needed and run the export_card_schema example",
+        path.display()
+    );
+}
+
+#[test]
+fn every_fixture_validates() {
+    let validator = jsonschema::validator_for(&card_json_schema()).unwrap();
+    let mut failures: Vec<String> = Vec::new();
+
+    let files = snapshot_files(Path::new("tests/fixtures")).into_iter().chain(snapshot_files(Path::new("tests/synthetic")));
+    for path in files {
+        let card: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let errors = validation_errors(&validator, &card);
+
+        if !errors.is_empty() {
+            failures.push(format!("{}:\n  {}", path.display(), errors.join("\n  ")));
+        }
+    }
+
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn validator_rejects_broken_cards() {
+    let validator = jsonschema::validator_for(&card_json_schema()).unwrap();
+    let mut card: Value = serde_json::from_str(
+        &fs::read_to_string("tests/fixtures/signi/WXDi-P12-071.json").unwrap()
+    ).unwrap();
+    card["card_type"] = Value::String("Dragon".into());
+    card.as_object_mut().unwrap().remove("skill");
+
+    let errors = validation_errors(&validator, &card);
+    assert_eq!(errors.len(), 2, "{:?}", errors);
+}
@@ -0,0 +1,75 @@
+// 結合テストで共有する補助関数。使わないテストもあるので dead_code は許す
+#![allow(dead_code)]
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+
+// テストごとの空の一時ディレクトリ。前回の実行の残りは消す
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("scraping_sample_{}_{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// テスト用サーバーが受けたリクエスト。ヘッダー名は小文字にしてある
+pub struct Request {
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    // application/x-www-form-urlencoded の値(テストで送るのはASCIIの値だけ)
+    pub fn form_value(&self, name: &str) -> Option<String> {
+        String::from_utf8_lossy(&self.body)
+            .split('&')
+            .find_map(|pair| pair.strip_prefix(&format!("{}=", name)).map(|value| value.to_string()))
+    }
+}
+
+// 接続ごとに一つのリクエストを読み、handler の返した (ステータス, 本文) を返す最小限のHTTPサーバー。
+// 戻り値は "http://127.0.0.1:<port>"
+pub fn serve<F>(handler: F) -> String
+where
+    F: Fn(&Request) -> (&'static str, Vec<u8>) + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut headers: Vec<(String, String)> = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((key, value)) = line.split_once(':') {
+                    headers.push((key.trim().to_lowercase(), value.trim().to_string()));
+                }
+            }
+
+            let mut request = Request { headers, body: Vec::new() };
+            let content_length: usize = request.header("content-length").and_then(|l| l.parse().ok()).unwrap_or(0);
+            request.body = vec![0; content_length];
+            reader.read_exact(&mut request.body).unwrap();
+
+            let (status, body) = handler(&request);
+            write!(stream, "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", status, body.len()).unwrap();
+            stream.write_all(&body).unwrap();
+        }
+    });
+
+    base
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use scraping_sample::export::{CardPool, ExportFormat};
+use std::path::Path;
+
+fn export(pool: &CardPool, format: ExportFormat) -> String {
+    let mut out: Vec<u8> = Vec::new();
+    pool.write(format, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn export_fixture_pool() {
+    let pool = CardPool::from_cache_dir(Path::new("tests/fixtures"));
+    assert!(pool.failures.is_empty(), "{:?}", pool.failures);
+    assert!(!pool.cards.is_empty());
+
+    let jsonl = export(&pool, ExportFormat::JsonLines);
+    assert_eq!(jsonl.lines().count(), pool.cards.len());
+    for line in jsonl.lines() {
+        serde_json::from_str::<serde_json::Value>(line).unwrap();
+    }
+
+    let bundle: serde_json::Value = serde_json::from_str(&export(&pool, ExportFormat::Bundle)).unwrap();
+    assert_eq!(bundle["count"], pool.cards.len());
+    assert_eq!(bundle["cards"].as_array().unwrap().len(), pool.cards.len());
+
+    let csv = export(&pool, ExportFormat::Csv);
+    let header = csv.lines().next().unwrap();
+    assert!(header.starts_with("no,name,pronounce,card_type"));
+    assert!(header.ends_with("feature_bits_low,feature_bits_high,features,life_burst,skill"));
+
+    // WXDi-P12-071 はドローと凍結を持つ
+    let row = csv.lines().find(|l| l.starts_with("WXDi-P12-071,")).unwrap();
+    assert!(row.contains("ドロー"), "{}", row);
+}
+
+#[test]
+fn unparsable_pages_are_reported() {
+    let dir = common::temp_dir("export");
+    std::fs::write(dir.join("broken.html"), r#"<div class="cardData"><dl><dd>ドラゴン</dd></dl></div>"#).unwrap();
+    let signi = std::fs::read_to_string("tests/fixtures/signi/WX22-025.html").unwrap();
+    std::fs::write(dir.join("no_number.html"), signi.replace("class=\"cardNum\"", "class=\"cardNumber\"")).unwrap();
+
+    let pool = CardPool::from_cache_dir(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert!(pool.cards.is_empty());
+    let mut reasons: Vec<&str> = pool.failures.iter().map(|f| f.reason.as_str()).collect();
+    reasons.sort();
+    assert_eq!(reasons, vec!["card number not found", "card type not detected"]);
+}
+
+// 読めなかったファイルも、理由とともに失敗として記録する
+#[test]
+fn unreadable_files_are_reported() {
+    let dir = common::temp_dir("export_unreadable");
+    std::fs::copy("tests/fixtures/signi/WX22-025.html", dir.join("025.html")).unwrap();
+    std::fs::write(dir.join("latin1.html"), b"<div class=\"cardData\">\xff\xfe</div>").unwrap();
+
+    let pool = CardPool::from_cache_dir(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(pool.cards.len(), 1);
+    assert_eq!(pool.failures.len(), 1);
+    assert_eq!(pool.failures[0].path, dir.join("latin1.html"));
+    assert!(pool.failures[0].reason.contains("UTF-8"), "{}", pool.failures[0].reason);
+}
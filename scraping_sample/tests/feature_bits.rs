@@ -0,0 +1,54 @@
+use scraping_sample::wixoss::constants::CardFeature;
+use scraping_sample::wixoss::feature_set::FeatureSet;
+use std::collections::HashSet;
+
+#[test]
+fn bit_positions_are_unique_and_fit() {
+    let mut seen: HashSet<u32> = HashSet::new();
+
+    for (i, feature) in CardFeature::ALL.iter().enumerate() {
+        let position = feature.bit_position();
+        assert!(position < 128, "{:?} does not fit in FeatureSet", feature);
+        assert!(seen.insert(position), "{:?} shares bit {} with another feature", feature, position);
+        // ALLは宣言順に並んでいること
+        assert_eq!(position as usize, i, "{:?} is out of order in CardFeature::ALL", feature);
+    }
+}
+
+#[test]
+fn feature_set_queries() {
+    let set: FeatureSet = [CardFeature::DoubleCrush, CardFeature::Draw, CardFeature::BanishOnAttack]
+        .into_iter()
+        .collect();
+
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(&CardFeature::BanishOnAttack));
+    assert!(!set.contains(&CardFeature::Lancer));
+
+    let wanted: FeatureSet = [CardFeature::DoubleCrush, CardFeature::Draw].into_iter().collect();
+    assert!(set.contains_all(&wanted));
+
+    let other: FeatureSet = [CardFeature::Lancer, CardFeature::Draw].into_iter().collect();
+    assert!(!set.contains_all(&other));
+    assert!(set.contains_any(&other));
+
+    assert_eq!(FeatureSet::from_words(set.to_words()), set);
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        vec![CardFeature::DoubleCrush, CardFeature::Draw, CardFeature::BanishOnAttack]
+    );
+}
+
+#[test]
+fn implied_parent_features() {
+    assert_eq!(CardFeature::SLancer.parents(), &[CardFeature::Lancer]);
+    assert!(CardFeature::Lancer.with_descendants().contains(&CardFeature::SLancer));
+
+    let set: FeatureSet = [CardFeature::SLancer, CardFeature::RandomDiscard].into_iter().collect();
+    assert!(!set.contains(&CardFeature::Lancer));
+
+    let implied = set.with_implied();
+    assert!(implied.contains(&CardFeature::Lancer));
+    assert!(implied.contains(&CardFeature::DiscardOpponent));
+    assert!(!implied.contains(&CardFeature::Salvage));
+}
@@ -0,0 +1,32 @@
+use scraping_sample::report::FeatureReport;
+use scraping_sample::wixoss::constants::CardFeature;
+use std::path::Path;
+
+// 検出ルールがまだ無いフィーチャー
+const NOT_DETECTED: [CardFeature; 3] = [CardFeature::Up, CardFeature::Ener, CardFeature::AttackNoEffect];
+
+// すべてのフィーチャーが、少なくとも一枚のフィクスチャカード自身の能力から検出されること
+// (他のカードに与える能力からの検出は数えない)。実カードのページが無いものは手書きのページ(tests/synthetic)で数える
+#[test]
+fn every_feature_is_detected_by_some_fixture() {
+    let reports = [
+        FeatureReport::from_cache_dir(Path::new("tests/fixtures")),
+        FeatureReport::from_cache_dir(Path::new("tests/synthetic")),
+    ];
+    for report in &reports {
+        assert!(report.parse_failures.is_empty(), "{:?}", report.parse_failures);
+    }
+    let is_detected = |f: &CardFeature| reports.iter().any(|report| report.feature_counts.contains_key(f));
+
+    let missing: Vec<&CardFeature> = CardFeature::ALL.iter()
+        .filter(|f| !NOT_DETECTED.contains(f) && !f.is_deprecated())
+        .filter(|f| !is_detected(f))
+        .collect();
+
+    assert!(missing.is_empty(), "features never detected: {:?}", missing);
+
+    let detected: Vec<&CardFeature> = NOT_DETECTED.iter()
+        .filter(|f| is_detected(f))
+        .collect();
+    assert!(detected.is_empty(), "remove from NOT_DETECTED: {:?}", detected);
+}
@@ -0,0 +1,74 @@
+use scraping_sample::wixoss::constants::CardFeature;
+use scraping_sample::wixoss::feature_detail::{FeatureDetail, TargetSide};
+
+fn detail(feature: CardFeature, amount: u32, target: TargetSide) -> FeatureDetail {
+    FeatureDetail { feature, amount, target }
+}
+
+#[test]
+fn amounts_with_fullwidth_digits() {
+    assert_eq!(FeatureDetail::from_line("カードを２枚引く。"), vec![detail(CardFeature::Draw, 2, TargetSide::Own)]);
+    assert_eq!(FeatureDetail::from_line("【エナチャージ１】"), vec![detail(CardFeature::Charge, 1, TargetSide::Own)]);
+    assert_eq!(
+        FeatureDetail::from_line("あなたのデッキの上からカードを３枚トラッシュに置く。"),
+        vec![detail(CardFeature::Drop, 3, TargetSide::Own)]
+    );
+    // 正規化済みの半角数字も同じ
+    assert_eq!(FeatureDetail::from_line("カードを2枚引く。"), FeatureDetail::from_line("カードを２枚引く。"));
+}
+
+#[test]
+fn power_signs() {
+    // 全角マイナス、マイナス記号、ハイフンのどれでも読む
+    for minus in ['－', '−', '-'] {
+        let line = format!("対戦相手のシグニ１体を対象とし、ターン終了時まで、それのパワーを{}8000する。", minus);
+        assert_eq!(FeatureDetail::from_line(&line), vec![detail(CardFeature::PowerDown, 8000, TargetSide::Opponent)], "{}", line);
+    }
+    assert_eq!(
+        FeatureDetail::from_line("あなたのシグニ１体を対象とし、ターン終了時まで、それのパワーを＋5000する。"),
+        vec![detail(CardFeature::PowerUp, 5000, TargetSide::Own)]
+    );
+}
+
+#[test]
+fn target_side() {
+    // 同じ文の中で最後に出てきた方
+    assert_eq!(
+        FeatureDetail::from_line("あなたの手札を１枚捨て、対戦相手のシグニ１体を対象とし、それのパワーを－3000する。"),
+        vec![detail(CardFeature::PowerDown, 3000, TargetSide::Opponent)]
+    );
+    // 前の文の「対戦相手」は見ない
+    assert_eq!(
+        FeatureDetail::from_line("対戦相手のシグニ１体をダウンする。このシグニのパワーを＋2000する。"),
+        vec![detail(CardFeature::PowerUp, 2000, TargetSide::Own)]
+    );
+    // 書かれていなければパターンごとの既定値
+    assert_eq!(FeatureDetail::from_line("ライフクロス１枚をクラッシュする。"), vec![detail(CardFeature::LifeCrush, 1, TargetSide::Opponent)]);
+    assert_eq!(
+        FeatureDetail::from_line("ターン終了時まで、それのパワーを－1000する。"),
+        vec![detail(CardFeature::PowerDown, 1000, TargetSide::Unspecified)]
+    );
+    // 誘発条件(「…とき、」の前)の「対戦相手」は対象ではない
+    assert_eq!(
+        FeatureDetail::from_line("このシグニが対戦相手の、能力か効果の対象になったとき、カードを1枚引く。"),
+        vec![detail(CardFeature::Draw, 1, TargetSide::Own)]
+    );
+    assert_eq!(
+        FeatureDetail::from_line("このシグニがアタックしたとき、対戦相手のシグニ１体を対象とし、それのパワーを－2000する。"),
+        vec![detail(CardFeature::PowerDown, 2000, TargetSide::Opponent)]
+    );
+    // 「：」の左側はコストなので自分側
+    let details = FeatureDetail::from_line("デッキの上からカードを２枚トラッシュに置く：ターン終了時まで、それのパワーを＋3000する。");
+    assert!(details.contains(&detail(CardFeature::Drop, 2, TargetSide::Own)), "{:?}", details);
+}
+
+#[test]
+fn several_amounts_in_one_line() {
+    // パターンごとに、行の中での出現順に並ぶ
+    let details = FeatureDetail::from_line("カードを１枚引く。対戦相手のシグニ１体を対象とし、それのパワーを－5000する。カードを２枚引く。");
+    assert_eq!(details, vec![
+        detail(CardFeature::Draw, 1, TargetSide::Own),
+        detail(CardFeature::Draw, 2, TargetSide::Own),
+        detail(CardFeature::PowerDown, 5000, TargetSide::Opponent),
+    ]);
+}
@@ -0,0 +1,35 @@
+// フロントエンド向けに書き出すビット表のスナップショット。
+// フィーチャーを追加・変更したときは
+//     BLESS=1 cargo test -p scraping_sample --test feature_index
+// で更新し、差分をレビューしてからコミットすること。
+
+use scraping_sample::wixoss::feature_index::{feature_index_json, feature_index_typescript};
+use std::fs;
+use std::path::Path;
+
+const SNAPSHOT_DIR: &str = "tests/feature_index";
+
+fn check_snapshot(file: &str, actual: &str) {
+    let path = Path::new(SNAPSHOT_DIR).join(file);
+    if std::env::var("BLESS").is_ok() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|_| panic!("{}: snapshot missing (run with BLESS=1)", path.display()));
+    for (i, (e, a)) in expected.lines().zip(actual.lines()).enumerate() {
+        assert_eq!(e, a, "{}:{} differs", path.display(), i + 1);
+    }
+    assert_eq!(expected.lines().count(), actual.lines().count(), "{}: line count differs", path.display());
+}
+
+#[test]
+fn json_index_snapshot() {
+    check_snapshot("card_features.json", &feature_index_json());
+}
+
+#[test]
+fn typescript_index_snapshot() {
+    check_snapshot("card_features.ts", &feature_index_typescript());
+}
@@ -0,0 +1,198 @@
+// このファイルは自動生成されている。編集せず `cargo run --example export_feature_index` で再生成すること
+
+export type CardFeatureName =
+    | "DoubleCrush"
+    | "TripleCrush"
+    | "DiscardOpponent"
+    | "RandomDiscard"
+    | "Draw"
+    | "Assassin"
+    | "Freeze"
+    | "Drop"
+    | "OnDrop"
+    | "OnRefresh"
+    | "Lancer"
+    | "SLancer"
+    | "Penetrate"
+    | "NonAttackable"
+    | "Down"
+    | "Up"
+    | "Charge"
+    | "EnerAttack"
+    | "Trash"
+    | "Ener"
+    | "PowerUp"
+    | "PowerDown"
+    | "Bounce"
+    | "DeckBounce"
+    | "Salvage"
+    | "LifeBurst"
+    | "Shadow"
+    | "Invulnerable"
+    | "OnSpell"
+    | "OnArts"
+    | "OnPiece"
+    | "OnBanish"
+    | "Banish"
+    | "Guard"
+    | "OnGuard"
+    | "AttackNoEffect"
+    | "OnTouch"
+    | "Awake"
+    | "Exceed"
+    | "OnExceed"
+    | "AddLife"
+    | "OnBurst"
+    | "LifeTrash"
+    | "LifeCrush"
+    | "Damage"
+    | "OnLifeCrush"
+    | "Position"
+    | "Vanilla"
+    | "Untouchable"
+    | "TopCheck"
+    | "BottomCheck"
+    | "Barrier"
+    | "MultiEner"
+    | "LrigTrash"
+    | "Charm"
+    | "Craft"
+    | "Acce"
+    | "Rise"
+    | "Recollect"
+    | "SeekTop"
+    | "EraseSkill"
+    | "CancelDamage"
+    | "Reanimate"
+    | "AdditionalAttack"
+    | "UnGuardable"
+    | "SalvageSpell"
+    | "BanishOnAttack";
+
+export type FeatureCategory = "Keyword" | "Trigger" | "Removal" | "Control" | "Resource" | "Protection" | "Offense" | "Mechanic";
+
+export const FEATURE_CATEGORY_LABELS: Record<FeatureCategory, string> = {
+    Keyword: "キーワード能力",
+    Trigger: "誘発条件",
+    Removal: "除去",
+    Control: "妨害",
+    Resource: "リソース",
+    Protection: "防御",
+    Offense: "攻撃",
+    Mechanic: "メカニクス",
+};
+
+export interface CardFeatureBit {
+    name: CardFeatureName;
+    label: string;
+    category: FeatureCategory;
+    parents: CardFeatureName[];
+    deprecated: boolean;
+    position: number;
+    word: number;
+    bit: bigint;
+}
+
+export const WORD_COUNT = 2;
+
+export const CARD_FEATURES: Record<CardFeatureName, CardFeatureBit> = {
+    DoubleCrush: { name: "DoubleCrush", label: "ダブルクラッシュ", category: "Keyword", parents: [], deprecated: false, position: 0, word: 0, bit: 1n },
+    TripleCrush: { name: "TripleCrush", label: "トリプルクラッシュ", category: "Keyword", parents: [], deprecated: false, position: 1, word: 0, bit: 2n },
+    DiscardOpponent: { name: "DiscardOpponent", label: "手札破壊", category: "Removal", parents: [], deprecated: false, position: 2, word: 0, bit: 4n },
+    RandomDiscard: { name: "RandomDiscard", label: "ランダム手札破壊", category: "Removal", parents: ["DiscardOpponent"], deprecated: false, position: 3, word: 0, bit: 8n },
+    Draw: { name: "Draw", label: "ドロー", category: "Resource", parents: [], deprecated: false, position: 4, word: 0, bit: 16n },
+    Assassin: { name: "Assassin", label: "アサシン", category: "Keyword", parents: [], deprecated: false, position: 5, word: 0, bit: 32n },
+    Freeze: { name: "Freeze", label: "凍結", category: "Control", parents: [], deprecated: false, position: 6, word: 0, bit: 64n },
+    Drop: { name: "Drop", label: "デッキドロップ", category: "Resource", parents: [], deprecated: false, position: 7, word: 0, bit: 128n },
+    OnDrop: { name: "OnDrop", label: "デッキドロップ時", category: "Trigger", parents: [], deprecated: false, position: 8, word: 0, bit: 256n },
+    OnRefresh: { name: "OnRefresh", label: "リフレッシュ時", category: "Trigger", parents: [], deprecated: false, position: 9, word: 0, bit: 512n },
+    Lancer: { name: "Lancer", label: "ランサー", category: "Keyword", parents: [], deprecated: false, position: 10, word: 0, bit: 1024n },
+    SLancer: { name: "SLancer", label: "Sランサー", category: "Keyword", parents: ["Lancer"], deprecated: false, position: 11, word: 0, bit: 2048n },
+    Penetrate: { name: "Penetrate", label: "ガード不可(廃止)", category: "Offense", parents: [], deprecated: true, position: 12, word: 0, bit: 4096n },
+    NonAttackable: { name: "NonAttackable", label: "アタック不可", category: "Control", parents: [], deprecated: false, position: 13, word: 0, bit: 8192n },
+    Down: { name: "Down", label: "ダウン", category: "Control", parents: [], deprecated: false, position: 14, word: 0, bit: 16384n },
+    Up: { name: "Up", label: "アップ", category: "Resource", parents: [], deprecated: false, position: 15, word: 0, bit: 32768n },
+    Charge: { name: "Charge", label: "エナチャージ", category: "Resource", parents: [], deprecated: false, position: 16, word: 0, bit: 65536n },
+    EnerAttack: { name: "EnerAttack", label: "エナ破壊", category: "Removal", parents: [], deprecated: false, position: 17, word: 0, bit: 131072n },
+    Trash: { name: "Trash", label: "トラッシュ送り", category: "Removal", parents: [], deprecated: false, position: 18, word: 0, bit: 262144n },
+    Ener: { name: "Ener", label: "エナ送り", category: "Removal", parents: [], deprecated: false, position: 19, word: 0, bit: 524288n },
+    PowerUp: { name: "PowerUp", label: "パワーアップ", category: "Offense", parents: [], deprecated: false, position: 20, word: 0, bit: 1048576n },
+    PowerDown: { name: "PowerDown", label: "パワーダウン", category: "Removal", parents: [], deprecated: false, position: 21, word: 0, bit: 2097152n },
+    Bounce: { name: "Bounce", label: "バウンス", category: "Removal", parents: [], deprecated: false, position: 22, word: 0, bit: 4194304n },
+    DeckBounce: { name: "DeckBounce", label: "デッキバウンス", category: "Removal", parents: [], deprecated: false, position: 23, word: 0, bit: 8388608n },
+    Salvage: { name: "Salvage", label: "回収", category: "Resource", parents: [], deprecated: false, position: 24, word: 0, bit: 16777216n },
+    LifeBurst: { name: "LifeBurst", label: "ライフバースト", category: "Mechanic", parents: [], deprecated: false, position: 25, word: 0, bit: 33554432n },
+    Shadow: { name: "Shadow", label: "シャドウ", category: "Keyword", parents: [], deprecated: false, position: 26, word: 0, bit: 67108864n },
+    Invulnerable: { name: "Invulnerable", label: "バニッシュされない", category: "Protection", parents: [], deprecated: false, position: 27, word: 0, bit: 134217728n },
+    OnSpell: { name: "OnSpell", label: "スペル使用時", category: "Trigger", parents: [], deprecated: false, position: 28, word: 0, bit: 268435456n },
+    OnArts: { name: "OnArts", label: "アーツ使用時", category: "Trigger", parents: [], deprecated: false, position: 29, word: 0, bit: 536870912n },
+    OnPiece: { name: "OnPiece", label: "ピース使用時", category: "Trigger", parents: [], deprecated: false, position: 30, word: 0, bit: 1073741824n },
+    OnBanish: { name: "OnBanish", label: "バニッシュした時", category: "Trigger", parents: [], deprecated: false, position: 31, word: 0, bit: 2147483648n },
+    Banish: { name: "Banish", label: "バニッシュ", category: "Removal", parents: [], deprecated: false, position: 32, word: 0, bit: 4294967296n },
+    Guard: { name: "Guard", label: "ガード", category: "Protection", parents: [], deprecated: false, position: 33, word: 0, bit: 8589934592n },
+    OnGuard: { name: "OnGuard", label: "ガードした時", category: "Trigger", parents: [], deprecated: false, position: 34, word: 0, bit: 17179869184n },
+    AttackNoEffect: { name: "AttackNoEffect", label: "アタック無効", category: "Protection", parents: [], deprecated: false, position: 35, word: 0, bit: 34359738368n },
+    OnTouch: { name: "OnTouch", label: "対象になった時", category: "Trigger", parents: [], deprecated: false, position: 36, word: 0, bit: 68719476736n },
+    Awake: { name: "Awake", label: "覚醒", category: "Mechanic", parents: [], deprecated: false, position: 37, word: 0, bit: 137438953472n },
+    Exceed: { name: "Exceed", label: "エクシード", category: "Mechanic", parents: [], deprecated: false, position: 38, word: 0, bit: 274877906944n },
+    OnExceed: { name: "OnExceed", label: "エクシードした時", category: "Trigger", parents: [], deprecated: false, position: 39, word: 0, bit: 549755813888n },
+    AddLife: { name: "AddLife", label: "ライフクロス追加", category: "Protection", parents: [], deprecated: false, position: 40, word: 0, bit: 1099511627776n },
+    OnBurst: { name: "OnBurst", label: "ライフバースト発動時", category: "Trigger", parents: [], deprecated: false, position: 41, word: 0, bit: 2199023255552n },
+    LifeTrash: { name: "LifeTrash", label: "ライフクロストラッシュ送り", category: "Offense", parents: [], deprecated: false, position: 42, word: 0, bit: 4398046511104n },
+    LifeCrush: { name: "LifeCrush", label: "クラッシュ", category: "Offense", parents: [], deprecated: false, position: 43, word: 0, bit: 8796093022208n },
+    Damage: { name: "Damage", label: "ダメージ", category: "Offense", parents: [], deprecated: false, position: 44, word: 0, bit: 17592186044416n },
+    OnLifeCrush: { name: "OnLifeCrush", label: "クラッシュ時", category: "Trigger", parents: [], deprecated: false, position: 45, word: 0, bit: 35184372088832n },
+    Position: { name: "Position", label: "シグニゾーン移動", category: "Mechanic", parents: [], deprecated: false, position: 46, word: 0, bit: 70368744177664n },
+    Vanilla: { name: "Vanilla", label: "能力を持たない", category: "Mechanic", parents: [], deprecated: false, position: 47, word: 0, bit: 140737488355328n },
+    Untouchable: { name: "Untouchable", label: "効果を受けない", category: "Protection", parents: [], deprecated: false, position: 48, word: 0, bit: 281474976710656n },
+    TopCheck: { name: "TopCheck", label: "トップ確認", category: "Resource", parents: [], deprecated: false, position: 49, word: 0, bit: 562949953421312n },
+    BottomCheck: { name: "BottomCheck", label: "ボトム確認", category: "Resource", parents: [], deprecated: false, position: 50, word: 0, bit: 1125899906842624n },
+    Barrier: { name: "Barrier", label: "バリア獲得", category: "Protection", parents: [], deprecated: false, position: 51, word: 0, bit: 2251799813685248n },
+    MultiEner: { name: "MultiEner", label: "マルチエナ", category: "Keyword", parents: [], deprecated: false, position: 52, word: 0, bit: 4503599627370496n },
+    LrigTrash: { name: "LrigTrash", label: "ルリグトラッシュ", category: "Mechanic", parents: [], deprecated: false, position: 53, word: 0, bit: 9007199254740992n },
+    Charm: { name: "Charm", label: "チャーム", category: "Mechanic", parents: [], deprecated: false, position: 54, word: 0, bit: 18014398509481984n },
+    Craft: { name: "Craft", label: "クラフト", category: "Mechanic", parents: [], deprecated: false, position: 55, word: 0, bit: 36028797018963968n },
+    Acce: { name: "Acce", label: "アクセ", category: "Mechanic", parents: [], deprecated: false, position: 56, word: 0, bit: 72057594037927936n },
+    Rise: { name: "Rise", label: "ライズ", category: "Mechanic", parents: [], deprecated: false, position: 57, word: 0, bit: 144115188075855872n },
+    Recollect: { name: "Recollect", label: "リコレクト", category: "Mechanic", parents: [], deprecated: false, position: 58, word: 0, bit: 288230376151711744n },
+    SeekTop: { name: "SeekTop", label: "シーク", category: "Resource", parents: [], deprecated: false, position: 59, word: 0, bit: 576460752303423488n },
+    EraseSkill: { name: "EraseSkill", label: "能力消去", category: "Control", parents: [], deprecated: false, position: 60, word: 0, bit: 1152921504606846976n },
+    CancelDamage: { name: "CancelDamage", label: "ダメージ無効", category: "Protection", parents: [], deprecated: false, position: 61, word: 0, bit: 2305843009213693952n },
+    Reanimate: { name: "Reanimate", label: "トラッシュ場出し", category: "Resource", parents: [], deprecated: false, position: 62, word: 0, bit: 4611686018427387904n },
+    AdditionalAttack: { name: "AdditionalAttack", label: "追加アタック", category: "Offense", parents: [], deprecated: false, position: 63, word: 0, bit: 9223372036854775808n },
+    UnGuardable: { name: "UnGuardable", label: "ガード不可", category: "Offense", parents: [], deprecated: false, position: 64, word: 1, bit: 1n },
+    SalvageSpell: { name: "SalvageSpell", label: "スペル回収", category: "Resource", parents: ["Salvage"], deprecated: false, position: 65, word: 1, bit: 2n },
+    BanishOnAttack: { name: "BanishOnAttack", label: "アタック時バニッシュ", category: "Removal", parents: ["Banish"], deprecated: false, position: 66, word: 1, bit: 4n },
+};
+
+// FeatureSet の decimal 表現([下位ワード, 上位ワード])を読み込む
+export const parseWords = (decimal: string[]): bigint[] => decimal.map((w) => BigInt(w));
+
+export const toWords = (names: CardFeatureName[]): bigint[] => {
+    const words: bigint[] = new Array(WORD_COUNT).fill(0n);
+    names.forEach((name) => {
+        const f = CARD_FEATURES[name];
+        words[f.word] |= f.bit;
+    });
+    return words;
+};
+
+export const containsAll = (words: bigint[], condition: bigint[]): boolean =>
+    condition.every((c, i) => (words[i] & c) === c);
+
+export const containsAny = (words: bigint[], condition: bigint[]): boolean =>
+    condition.some((c, i) => (words[i] & c) !== 0n);
+
+export const descendantsOf = (name: CardFeatureName): CardFeatureName[] => {
+    const found: CardFeatureName[] = [name];
+    for (let i = 0; i < found.length; i++) {
+        (Object.keys(CARD_FEATURES) as CardFeatureName[])
+            .filter((n) => CARD_FEATURES[n].parents.includes(found[i]) && !found.includes(n))
+            .forEach((n) => found.push(n));
+    }
+    return found;
+};
+
+// 親フィーチャーでの検索では子フィーチャーを持つカードもヒットさせる
+export const hasFeature = (words: bigint[], name: CardFeatureName): boolean =>
+    containsAny(words, toWords(descendantsOf(name)));
@@ -0,0 +1,188 @@
+// tests/fixtures/<カード種類>/<カード番号>.html をパースし、同名の .json スナップショットと比較する。
+// ルールを変更してスナップショットを更新するときは
+//     BLESS=1 cargo test -p scraping_sample --test golden
+// を実行し、差分をレビューしてからコミットすること。
+// tests/fixtures にはサイトから取得した実際の詳細ページだけを置く。
+// 実カードのページが手元に無い検出ルールは、手書きのページ(SYN-)を tests/synthetic に置いて同じ方法で確認する。
+
+use scraping_sample::wixoss::{Card, CardParseError, Signi, WixossCard};
+use scraping_sample::wixoss::reading::ReadingKeys;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const FIXTURE_DIR: &str = "tests/fixtures";
+const SYNTHETIC_DIR: &str = "tests/synthetic";
+
+fn fixture_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = Vec::new();
+
+    for entry in fs::read_dir(dir).expect("fixture dir read error") {
+        let path: PathBuf = entry.unwrap().path();
+        if path.is_dir() {
+            files.extend(fixture_files(&path));
+        } else if path.extension().is_some_and(|ext| ext == "html") {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    files
+}
+
+fn render_snapshot(html: &str) -> Option<String> {
+    Card::card_from_html(html).ok().map(|card| {
+        let mut json = serde_json::to_string_pretty(&card).unwrap();
+        json.push('\n');
+        json
+    })
+}
+
+// 行単位のLCSで期待値と実際の出力の差分を作る
+fn line_diff(expected: &str, actual: &str) -> String {
+    let a: Vec<&str> = expected.lines().collect();
+    let b: Vec<&str> = actual.lines().collect();
+
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out: Vec<String> = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            i += 1;
+            j += 1;
+        } else if j < b.len() && (i == a.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            out.push(format!("+ {}", b[j]));
+            j += 1;
+        } else {
+            out.push(format!("- {}", a[i]));
+            i += 1;
+        }
+    }
+    out.join("\n")
+}
+
+fn check_snapshots(dir: &str) {
+    let bless: bool = std::env::var("BLESS").is_ok();
+    let files: Vec<PathBuf> = fixture_files(Path::new(dir));
+    assert!(!files.is_empty(), "no fixtures found in {}", dir);
+
+    let mut failures: Vec<String> = Vec::new();
+
+    for html_path in files {
+        let html: String = fs::read_to_string(&html_path).unwrap();
+        let snapshot_path: PathBuf = html_path.with_extension("json");
+
+        let actual: String = match render_snapshot(&html) {
+            Some(json) => json,
+            None => {
+                failures.push(format!("{}: card type not detected", html_path.display()));
+                continue;
+            }
+        };
+
+        if bless {
+            fs::write(&snapshot_path, &actual).unwrap();
+            continue;
+        }
+
+        match fs::read_to_string(&snapshot_path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!(
+                "{}:\n{}",
+                snapshot_path.display(),
+                line_diff(&expected, &actual)
+            )),
+            Err(_) => failures.push(format!(
+                "{}: snapshot missing (run with BLESS=1)",
+                snapshot_path.display()
+            )),
+        }
+    }
+
+    if !failures.is_empty() {
+        panic!("{} snapshot(s) differ:\n\n{}", failures.len(), failures.join("\n\n"));
+    }
+}
+
+#[test]
+fn golden_cards() {
+    check_snapshots(FIXTURE_DIR);
+}
+
+// 手書きのページ。ルールが書いた文面に合うことしか確かめられないので、実カードのページが入手できたら fixtures に移す
+#[test]
+fn synthetic_cards() {
+    check_snapshots(SYNTHETIC_DIR);
+}
+
+// スナップショットのJSONを読み戻して書き出すと、元と同じJSONになること
+#[test]
+fn snapshots_round_trip() {
+    let files = fixture_files(Path::new(FIXTURE_DIR)).into_iter().chain(fixture_files(Path::new(SYNTHETIC_DIR)));
+    for html_path in files {
+        let snapshot_path: PathBuf = html_path.with_extension("json");
+        let expected: String = match fs::read_to_string(&snapshot_path) {
+            Ok(expected) => expected,
+            Err(_) => continue,
+        };
+
+        let card: Card = serde_json::from_str(&expected)
+            .unwrap_or_else(|e| panic!("{}: {}", snapshot_path.display(), e));
+        let mut actual = serde_json::to_string_pretty(&card).unwrap();
+        actual.push('\n');
+
+        assert!(expected == actual, "{}:\n{}", snapshot_path.display(), line_diff(&expected, &actual));
+    }
+}
+
+// 以前の書き出し形式(timeが連結文字列、storyが文字列、後から追加したフィールドなし)も読めること
+#[test]
+fn legacy_json_is_readable() {
+    let legacy = r#"{
+        "no": "WXDi-P12-071", "name": "コードイート　マチャフラ//ディソナ", "pronounce": "コードイートマチャフラディソナ",
+        "artist": "志月", "card_type": "Signi", "klass": "奏械：調理", "color": "緑", "level": "1", "cost": "",
+        "limit": "", "power": "3000", "user": "", "time": "メインフェイズ, アタックフェイズ", "story": "ディソナ",
+        "format": "DivaSelection", "rarity": "C", "skill": ["【出】カードを1枚引く。"], "features": ["Draw"]
+    }"#;
+
+    let card: Card = serde_json::from_str(legacy).unwrap();
+    let json: serde_json::Value = serde_json::to_value(&card).unwrap();
+
+    assert!(card.is_dissona());
+    assert_eq!(json["is_dissona"], true);
+    assert_eq!(card.reading(), &ReadingKeys::from_pronounce("コードイートマチャフラディソナ"));
+    assert!(card.matches_reading("こーどいーと"));
+    assert_eq!(json["time"], serde_json::json!(["メインフェイズ", "アタックフェイズ"]));
+    assert_eq!(json["level"], "1");
+    assert_eq!(json["cost"], "");
+}
+
+// 詳細ページとして壊れているものはpanicせずにエラーを返すこと
+#[test]
+fn malformed_pages_are_errors() {
+    let html = fs::read_to_string("tests/fixtures/signi/WX22-025.html").unwrap();
+
+    let without_no = html.replace("class=\"cardNum\"", "class=\"cardNumber\"");
+    assert_eq!(Card::card_from_html(&without_no).unwrap_err(), CardParseError::MissingCardNo);
+
+    let invalid_no = html.replace("WX22-025</", "-</");
+    assert!(matches!(Card::card_from_html(&invalid_no), Err(CardParseError::InvalidCardNo(_))));
+    assert!(matches!(Card::describe_html(&invalid_no), Err(CardParseError::InvalidCardNo(_))));
+    assert!(matches!(Signi::from_source(invalid_no), Err(CardParseError::InvalidCardNo(_))));
+
+    let unknown_type = html.replacen("<dd>シグニ</dd>", "<dd>ドラゴン</dd>", 1);
+    assert_eq!(Card::card_from_html(&unknown_type).unwrap_err(), CardParseError::UnknownCardType);
+
+    let short = r#"<div class="cardData"><dl><dt>カード種類</dt><dd>シグニ</dd></dl></div><span class="cardNum">WX22-025</span>"#;
+    assert_eq!(Card::card_from_html(short).unwrap_err(), CardParseError::MissingCells(1));
+    assert_eq!(Card::card_from_html("<html></html>").unwrap_err(), CardParseError::UnknownCardType);
+}
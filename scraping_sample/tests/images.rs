@@ -0,0 +1,86 @@
+mod common;
+
+use common::temp_dir;
+use scraping_sample::images::ImageCache;
+use scraping_sample::wixoss::Card;
+use sha2::{Digest, Sha256};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const IMAGE: &[u8] = b"\xFF\xD8\xFF\xE0 not really a jpeg, but enough bytes to resume from the middle \xFF\xD9";
+
+// Rangeに対応したサーバー。受けたリクエストの数を数える
+fn serve_image() -> (String, Arc<AtomicUsize>) {
+    let requests = Arc::new(AtomicUsize::new(0));
+    let counter = requests.clone();
+
+    let base = common::serve(move |request| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        let range_start: Option<usize> = request.header("range")
+            .and_then(|range| range.strip_prefix("bytes="))
+            .and_then(|range| range.trim_end_matches('-').parse().ok());
+        match range_start {
+            Some(start) => ("206 Partial Content", IMAGE[start..].to_vec()),
+            None => ("200 OK", IMAGE.to_vec()),
+        }
+    });
+
+    (base, requests)
+}
+
+#[tokio::test]
+async fn download_resume_and_skip() {
+    let (base, requests) = serve_image();
+    let url = format!("{}/img/card/WXDi/WXDi-P12-071.jpg", base);
+    let dir = temp_dir("images");
+    let cache = ImageCache::new(&dir);
+    let client = reqwest::Client::new();
+
+    // 途中まで取得したデータを置いておくと、続きだけを取得する
+    let key = hex::encode(Sha256::digest(url.as_bytes()));
+    std::fs::create_dir_all(dir.join("partial")).unwrap();
+    std::fs::write(dir.join("partial").join(format!("{}.part", key)), &IMAGE[..20]).unwrap();
+
+    let path = cache.fetch(&client, &url).await.unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), IMAGE);
+    assert_eq!(path.file_name().unwrap().to_str().unwrap(), format!("{}.jpg", hex::encode(Sha256::digest(IMAGE))));
+    assert!(!dir.join("partial").join(format!("{}.part", key)).exists());
+
+    // 取得済みなら通信しない
+    assert_eq!(cache.fetch(&client, &url).await.unwrap(), path);
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    // 別のURLでも内容が同じなら本体は一つ
+    let other = cache.fetch(&client, &format!("{}/img/card/WXDi/WXDi-P12-071P.jpg", base)).await.unwrap();
+    assert_eq!(other, path);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn card_records_local_path() {
+    let (base, _) = serve_image();
+    let dir = temp_dir("card_images");
+    let cache = ImageCache::new(&dir);
+
+    let html = std::fs::read_to_string("tests/fixtures/signi/WXDi-P12-071.html").unwrap()
+        .replace("https://www.takaratomy.co.jp/products/wixoss", &base);
+    let mut card = Card::card_from_html(&html).unwrap();
+    assert_eq!(card.image().unwrap().url, format!("{}/img/card/WXDi/WXDi-P12-071.jpg", base));
+    assert!(!cache.attach(&mut card));
+    assert_eq!(card.image().unwrap().local_path, None);
+
+    let path = cache.fetch_card_image(&reqwest::Client::new(), &mut card).await.unwrap().unwrap();
+    // キャッシュの root からの相対パスで記録する
+    let key = path.strip_prefix(&dir).unwrap();
+    assert!(key.starts_with("objects"));
+    assert_eq!(card.image().unwrap().local_path.as_deref(), Some(key.to_str().unwrap()));
+    assert_eq!(cache.local_path(&card), Some(path.clone()));
+
+    // 読み直したカードにも、取得済みの画像を記録できる
+    let mut reparsed = Card::card_from_html(&html).unwrap();
+    assert!(cache.attach(&mut reparsed));
+    assert_eq!(reparsed.image(), card.image());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,63 @@
+use scraping_sample::wixoss::Card;
+use scraping_sample::wixoss::constants::CardFeature;
+use scraping_sample::wixoss::feature_detail::TargetSide;
+use std::collections::HashSet;
+
+fn load(fixture: &str) -> String {
+    std::fs::read_to_string(format!("tests/fixtures/{}", fixture)).unwrap()
+}
+
+// ライフバーストの効果のフィーチャーは、カード自身のフィーチャーと混ぜない
+#[test]
+fn life_burst_features_are_kept_apart() {
+    let card = Card::card_from_html(&load("spell/WXDi-P14-061.html")).unwrap();
+    let life_burst = card.life_burst().unwrap();
+
+    assert_eq!(life_burst.text, "対戦相手のシグニを2体まで対象とし、それらをダウンする。");
+    assert_eq!(life_burst.features, HashSet::from([CardFeature::Down]));
+    assert!(card.features().contains(&CardFeature::LifeBurst));
+    assert!(!card.features().contains(&CardFeature::Down), "{:?}", card.features());
+    assert!(!life_burst.features.contains(&CardFeature::LifeBurst));
+}
+
+#[test]
+fn life_burst_details_are_kept_apart() {
+    let card = Card::card_from_html(&load("spell/WX11-042.html")).unwrap();
+    let life_burst = card.life_burst().unwrap();
+
+    assert_eq!(life_burst.details.len(), 1);
+    assert_eq!(life_burst.details[0].feature, CardFeature::LifeCrush);
+    assert_eq!(life_burst.details[0].target, TargetSide::Opponent);
+    assert!(card.feature_details().iter().all(|d| d.feature != CardFeature::LifeCrush), "{:?}", card.feature_details());
+}
+
+// 複数行のライフバースト(選択肢など)は、すべての行をライフバーストに持つ
+#[test]
+fn every_life_burst_line_is_kept() {
+    let html = load("signi/WXDi-P12-071.html").replace(
+        "対戦相手のシグニ１体を対象とし、それをダウンし凍結する。カードを１枚引く。",
+        "以下の２つから１つを選ぶ。<br />\n①対戦相手のシグニ１体を対象とし、それをダウンし凍結する。<br />\n②カードを２枚引く。",
+    );
+    let card = Card::card_from_html(&html).unwrap();
+    let life_burst = card.life_burst().unwrap();
+
+    assert_eq!(
+        life_burst.text,
+        "以下の2つから1つを選ぶ。\n①対戦相手のシグニ1体を対象とし、それをダウンし凍結する。\n②カードを2枚引く。"
+    );
+    assert_eq!(life_burst.features, HashSet::from([CardFeature::Freeze, CardFeature::Draw]));
+    assert_eq!(life_burst.details.iter().map(|d| d.amount).collect::<Vec<u32>>(), vec![2]);
+
+    // 選択肢の行がスキルに混ざらない
+    let original = Card::card_from_html(&load("signi/WXDi-P12-071.html")).unwrap();
+    assert_eq!(serde_json::to_value(&card).unwrap()["skill"], serde_json::to_value(&original).unwrap()["skill"]);
+    assert_eq!(card.features(), original.features());
+}
+
+#[test]
+fn card_without_life_burst() {
+    let card = Card::card_from_html(&load("signi/WXDi-P14-040.html")).unwrap();
+    assert!(card.life_burst().is_none());
+    assert!(!card.has_life_burst());
+    assert!(!card.features().contains(&CardFeature::LifeBurst));
+}
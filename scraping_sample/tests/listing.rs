@@ -0,0 +1,46 @@
+use scraping_sample::listing::{Listing, ListingError, ListingPage};
+use std::path::Path;
+
+#[test]
+fn parse_listing_page() {
+    let html = std::fs::read_to_string("tests/listing/booster/WXDi-P14/p1.html").unwrap();
+    let page = ListingPage::from_html(&html);
+
+    assert_eq!(page.total, Some(3));
+    assert_eq!(page.entries.len(), 2);
+
+    let first = &page.entries[0];
+    assert_eq!(first.card_no.as_str(), "WXDi-P14-001");
+    assert_eq!(first.name.as_deref(), Some("羅星姫　ウリス"));
+    assert_eq!(first.rarity.as_deref(), Some("LR"));
+    assert_eq!(first.thumbnail.as_deref(), Some("https://www.takaratomy.co.jp/products/wixoss/img/card/WXDi/WXDi-P14-001.jpg"));
+    assert_eq!(first.link, "card_list.php?card=card_detail&card_no=WXDi-P14-001");
+
+    // 名前が無ければ画像のaltを使う
+    assert_eq!(page.entries[1].name.as_deref(), Some("夢限　－Ｑ－"));
+}
+
+#[test]
+fn listing_from_cached_pages() {
+    let listing = Listing::from_cache_dir(Path::new("tests/listing/booster/WXDi-P14")).unwrap();
+
+    let numbers: Vec<&str> = listing.entries.iter().map(|e| e.card_no.as_str()).collect();
+    assert_eq!(numbers, ["WXDi-P14-001", "WXDi-P14-006", "WXDi-P14-TK01"]);
+    assert_eq!(listing.links().len(), 3);
+    assert_eq!(listing.validate(), Ok(()));
+}
+
+#[test]
+fn missing_pages_are_detected() {
+    let html = std::fs::read_to_string("tests/listing/booster/WXDi-P14/p1.html").unwrap();
+    let listing = Listing::from_pages([ListingPage::from_html(&html)]);
+    assert_eq!(listing.validate(), Err(ListingError::CountMismatch { expected: 3, found: 2, unparsed: 0 }));
+
+    let broken = r#"<a class="c-box" href="card_list.php?card=card_detail">?</a>"#;
+    let listing = Listing::from_pages([ListingPage::from_html(broken)]);
+    assert_eq!(listing.unparsed, ["card_list.php?card=card_detail"]);
+    assert_eq!(listing.validate(), Err(ListingError::MissingTotal));
+
+    // カード番号を読めなかったリンクも落とさない
+    assert_eq!(listing.links(), ["card_list.php?card=card_detail"]);
+}
@@ -0,0 +1,11 @@
+use scraping_sample::wixoss::normalize::normalize_text;
+
+#[test]
+fn fullwidth_alphanumerics_and_halfwidth_kana() {
+    assert_eq!(normalize_text("Ｓランサー"), "Sランサー");
+    assert_eq!(normalize_text("カードを２枚引く"), "カードを2枚引く");
+    assert_eq!(normalize_text("ｺｰﾄﾞｲｰﾄ ｶﾞﾊﾟ"), "コードイート ガパ");
+
+    // 全角記号と漢字・かなはそのまま
+    assert_eq!(normalize_text("（パワーを－5000する）：【出】"), "（パワーを－5000する）：【出】");
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use scraping_sample::listing::Listing;
+use scraping_sample::products::{is_release_date, ProductCatalog, ProductKind};
+use scraping_sample::wixoss::card_no::CardNo;
+use scraping_sample::ProductType;
+use std::path::Path;
+
+fn no(s: &str) -> CardNo {
+    s.parse().unwrap()
+}
+
+#[test]
+fn catalog_from_listing() {
+    let listing = Listing::from_cache_dir(Path::new("tests/listing/booster/WXDi-P14")).unwrap();
+    let mut catalog = ProductCatalog::default();
+    catalog.add_listing(&ProductType::Booster("WXDi-P14".into()), &listing);
+
+    let product = catalog.product("WXDi-P14").unwrap();
+    assert_eq!(product.kind, ProductKind::Booster);
+    assert_eq!(product.card_count, Some(3));
+
+    let cards: Vec<String> = catalog.cards_of("WXDi-P14").iter().map(|no| no.to_string()).collect();
+    assert_eq!(cards, vec!["WXDi-P14-001", "WXDi-P14-006", "WXDi-P14-TK01"]);
+    assert!(catalog.contains("WXDi-P14", &no("WXDi-P14-006")));
+}
+
+#[test]
+fn reprint_belongs_to_both_products() {
+    let mut catalog = ProductCatalog::default();
+    catalog.link("WXDi-P14", no("WXDi-P14-001"));
+    catalog.link("WXDi-D09", no("WXDi-P14-001"));
+
+    assert_eq!(catalog.product_codes_of(&no("WXDi-P14-001")), vec!["WXDi-D09", "WXDi-P14"]);
+    assert!(catalog.contains("WXDi-D09", &no("WXDi-P14-001")));
+    assert_eq!(catalog.cards_of("WXDi-D09"), vec![&no("WXDi-P14-001")]);
+}
+
+#[test]
+fn unknown_card_falls_back_to_card_no() {
+    let catalog = ProductCatalog::default();
+    assert_eq!(catalog.product_codes_of(&no("WXDi-P12-071")), vec!["WXDi-P12"]);
+    assert!(catalog.contains("WXDi-P12", &no("WXDi-P12-071")));
+    assert!(!catalog.contains("WXDi-P14", &no("WXDi-P12-071")));
+
+    // プロモーションカードは crawl promotion で登録される商品と同じコード
+    assert_eq!(catalog.product_codes_of(&no("PR-K060")), vec![ProductType::PromotionCard.code()]);
+}
+
+#[test]
+fn describe_known_product() {
+    let listing = Listing::from_cache_dir(Path::new("tests/listing/booster/WXDi-P14")).unwrap();
+    let mut catalog = ProductCatalog::default();
+    catalog.add_listing(&ProductType::Booster("WXDi-P14".into()), &listing);
+
+    assert!(catalog.describe("WXDi-P14", Some("ブースターパック FORTH DIVA"), None));
+    assert!(catalog.describe("WXDi-P14", None, Some("2021-09-25")));
+    let product = catalog.product("WXDi-P14").unwrap();
+    assert_eq!(product.name.as_deref(), Some("ブースターパック FORTH DIVA"));
+    assert_eq!(product.release_date.as_deref(), Some("2021-09-25"));
+    assert_eq!(product.kind.as_str(), "Booster");
+
+    // 件数を更新しても名前・発売日は残る
+    catalog.add_listing(&ProductType::Booster("WXDi-P14".into()), &listing);
+    assert_eq!(catalog.product("WXDi-P14").unwrap().release_date.as_deref(), Some("2021-09-25"));
+
+    assert!(!catalog.describe("WXDi-P15", Some("?"), None));
+    assert!(is_release_date("2021-09-25"));
+    assert!(!is_release_date("2021/09/25"));
+    assert!(!is_release_date("2024-99-99"));
+    assert!(!is_release_date("2023-02-29"));
+    assert!(is_release_date("2024-02-29"));
+    assert!(!is_release_date("2024-2-9"));
+}
+
+#[test]
+fn save_and_load() {
+    let path = common::temp_dir("products").join("products.json");
+    assert_eq!(ProductCatalog::load(&path).unwrap(), ProductCatalog::default());
+
+    let listing = Listing::from_cache_dir(Path::new("tests/listing/booster/WXDi-P14")).unwrap();
+    let mut catalog = ProductCatalog::default();
+    catalog.add_listing(&ProductType::Booster("WXDi-P14".into()), &listing);
+    catalog.link("promotion", no("WXDi-P14-001"));
+    catalog.save(&path).unwrap();
+
+    assert_eq!(ProductCatalog::load(&path).unwrap(), catalog);
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
@@ -0,0 +1,36 @@
+use scraping_sample::wixoss::reading::{to_romaji, ReadingKeys};
+
+#[test]
+fn reading_forms() {
+    let keys = ReadingKeys::from_pronounce("コードイートマチャフラディソナ");
+    assert_eq!(keys.hiragana, "こーどいーとまちゃふらでぃそな");
+    assert_eq!(keys.katakana, "コードイートマチャフラディソナ");
+    assert_eq!(keys.romaji, "koodoiitomachafuradisona");
+
+    assert!(keys.starts_with("こーど"));
+    assert!(keys.starts_with("コード"));
+    assert!(keys.starts_with("Kood"));
+    assert!(!keys.starts_with("まちゃ"));
+}
+
+#[test]
+fn hepburn_romaji() {
+    assert_eq!(to_romaji("シンジツ"), "shinjitsu");
+    assert_eq!(to_romaji("ピッチ"), "pitchi");
+    assert_eq!(to_romaji("ガッツ"), "gattsu");
+    assert_eq!(to_romaji("ジュウ"), "juu");
+}
+
+#[test]
+fn gojuon_collation() {
+    let mut readings: Vec<ReadingKeys> = ["バン", "ハナビ", "ハナ", "アイ", "パス", "ハン"]
+        .iter()
+        .map(|p| ReadingKeys::from_pronounce(p))
+        .collect();
+    readings.sort_by(|a, b| a.collation.cmp(&b.collation));
+
+    let sorted: Vec<&str> = readings.iter().map(|r| r.katakana.as_str()).collect();
+    assert_eq!(sorted, vec!["アイ", "パス", "ハナ", "ハナビ", "ハン", "バン"]);
+
+    assert_eq!(ReadingKeys::from_pronounce("バン").collation, "はん ばん");
+}
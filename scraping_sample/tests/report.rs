@@ -0,0 +1,79 @@
+mod common;
+
+use scraping_sample::report::FeatureReport;
+use scraping_sample::wixoss::CardType;
+use scraping_sample::wixoss::constants::CardFeature;
+use std::fs;
+use std::path::PathBuf;
+
+// フィクスチャの一部をキャッシュディレクトリと同じ形で置く
+fn cache_dir(name: &str, fixtures: &[&str]) -> PathBuf {
+    let dir = common::temp_dir(name);
+    for fixture in fixtures {
+        let from = PathBuf::from("tests/fixtures").join(fixture);
+        fs::copy(&from, dir.join(from.file_name().unwrap())).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn report_counts_cards_and_failures() {
+    let dir = cache_dir("report", &[
+        "signi/WXDi-P14-040.html",
+        "token/WDA-F01-CO-01.html",
+        "spell/WX11-042.html",
+        "token/WXDi-P12-TK01B.html",
+    ]);
+    // カード種類を判定できないページはパース失敗として数える
+    let broken = fs::read_to_string("tests/fixtures/signi/WXDi-P14-040.html").unwrap()
+        .replace("<dd>シグニ</dd>", "<dd>不明</dd>");
+    fs::write(dir.join("broken.html"), broken).unwrap();
+    // カード詳細ページでないものは読まない
+    fs::write(dir.join("p1.html"), "<html><body><div class=\"cardDip\"></div></body></html>").unwrap();
+
+    let report = FeatureReport::from_cache_dir(&dir);
+
+    assert_eq!(report.total_cards, 4);
+    assert_eq!(report.parse_failures, vec![dir.join("broken.html")]);
+    assert_eq!(report.card_type_counts.get(&CardType::Signi), Some(&1));
+    assert_eq!(report.card_type_counts.get(&CardType::Token), Some(&2));
+    assert_eq!(report.card_type_counts.get(&CardType::Spell), Some(&1));
+    assert_eq!(report.feature_counts.get(&CardFeature::Assassin), Some(&1));
+    assert_eq!(report.feature_counts.get(&CardFeature::Vanilla), Some(&1));
+    assert_eq!(report.card_type_features[&CardType::Signi].get(&CardFeature::Freeze), Some(&1));
+
+    assert_eq!(report.life_burst_count, 1);
+    assert_eq!(report.life_burst_feature_counts.get(&CardFeature::LifeCrush), Some(&1));
+
+    // スキルはあるがフィーチャーが無いカードと、その行
+    assert_eq!(report.featureless_cards, vec!["WXDi-P12-TK01B"]);
+    assert!(!report.unclassified_lines.is_empty());
+    assert!(report.unclassified_lines.iter().all(|line| line.cards == vec!["WXDi-P12-TK01B"]));
+    assert!(report.unclassified_lines.windows(2).all(|w| w[0].count >= w[1].count));
+
+    let text = report.to_string();
+    assert!(text.starts_with("カード数\t:4\nパース失敗\t:1\n"), "{}", text);
+    for section in ["[フィーチャー別]", "[ライフバースト](1)", "[カード種類別]", "[フィーチャーなし](1)", "[未分類のスキル行]"] {
+        assert!(text.contains(section), "{} not in\n{}", section, text);
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+// 他のカードに与える能力は、カード自身のフィーチャーとも未分類の行とも別に数える
+#[test]
+fn granted_abilities_are_counted_apart() {
+    let dir = cache_dir("report_granted", &["key/PR-K075.html"]);
+    let report = FeatureReport::from_cache_dir(&dir);
+
+    assert_eq!(report.total_cards, 1);
+    assert_eq!(report.feature_counts.keys().collect::<Vec<_>>(), vec![&CardFeature::LrigTrash]);
+    assert_eq!(report.granted_feature_counts.get(&CardFeature::PowerDown), Some(&1));
+    assert_eq!(report.granted_feature_counts.get(&CardFeature::Drop), Some(&1));
+    assert!(!report.feature_counts.contains_key(&CardFeature::PowerDown));
+
+    // 与える能力の行(【自】：あなたのターン終了時、…)は未分類の行に出てこない
+    assert!(report.unclassified_lines.iter().all(|line| !line.line.contains("ターン終了時")), "{:?}", report.unclassified_lines);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use scraping_sample::{find_card_links, ProductType, SearchQuery};
+use std::sync::{Arc, Mutex};
+
+// 一覧ページ(1ページ21件)を返すサーバー。POSTされた card_page を記録する
+fn serve_listing(total: usize) -> (String, Arc<Mutex<Vec<usize>>>) {
+    let requested = Arc::new(Mutex::new(Vec::new()));
+    let pages = requested.clone();
+
+    let base = common::serve(move |request| {
+        let page: usize = request.form_value("card_page").unwrap().parse().unwrap();
+        pages.lock().unwrap().push(page);
+
+        let mut body = format!("<div class=\"cardDip\"><h3><p>検索結果<span>{}件</span></p></h3>", total);
+        for i in (page - 1) * 21..(page * 21).min(total) {
+            body.push_str(&format!(
+                "<a class=\"c-box\" href=\"/products/wixoss/card_list.php?card=card_detail&card_no=SYN-{:03}\">{}</a>",
+                i + 1, i + 1
+            ));
+        }
+        body.push_str("</div>");
+        ("200 OK", body.into_bytes())
+    });
+
+    (format!("{}/card_list.php", base), requested)
+}
+
+#[test]
+fn search_form() {
+    let form = SearchQuery::any_product()
+        .keyword("ドロー")
+        .card_kind("シグニ")
+        .rarity("LR")
+        .to_hashmap();
+
+    // 商品の一覧の取得で使ってきた項目だけを送る
+    let mut fields: Vec<&str> = form.keys().map(|k| k.as_str()).collect();
+    fields.sort();
+    assert_eq!(fields, ["card_kind", "card_page", "keyword", "product_no", "product_type", "rarelity", "search"]);
+
+    assert_eq!(form["search"], "");
+    assert_eq!(form["keyword"], "ドロー");
+    assert_eq!(form["card_kind"], "シグニ");
+    assert_eq!(form["rarelity"], "LR");
+    assert_eq!(form["product_type"], "");
+    assert_eq!(form["product_no"], "");
+    assert_eq!(form["card_page"], "1");
+}
+
+#[test]
+fn search_in_product() {
+    let form = SearchQuery::in_product(&ProductType::Booster("WXDi-P14".into())).rarity("SR").page(3).to_hashmap();
+
+    assert_eq!(form["product_type"], "booster");
+    assert_eq!(form["product_no"], "WXDi-P14");
+    assert_eq!(form["rarelity"], "SR");
+    assert_eq!(form["card_page"], "3");
+    assert_eq!(form["keyword"], "");
+}
+
+#[test]
+fn card_links_in_list_page() {
+    let html = r#"
+        <div class="cardDip">
+            <h3><p>検索結果<span>2件</span></p></h3>
+            <a class="c-box" href="/products/wixoss/card_list.php?card=card_detail&card_no=WXDi-P14-001">1</a>
+            <a class="c-box" href="/products/wixoss/card_list.php?card=card_detail&card_no=WXDi-P14-006">2</a>
+            <a class="other" href="/products/wixoss/">top</a>
+        </div>
+    "#;
+
+    assert_eq!(find_card_links(html), [
+        "/products/wixoss/card_list.php?card=card_detail&card_no=WXDi-P14-001",
+        "/products/wixoss/card_list.php?card=card_detail&card_no=WXDi-P14-006",
+    ]);
+}
+
+// 件数から必要なページ数を求め、2ページ目以降も取得する
+#[tokio::test]
+async fn listing_follows_every_page() {
+    let (endpoint, requested) = serve_listing(45);
+    let listing = SearchQuery::any_product().rarity("LR").endpoint(&endpoint).fetch_listing().await.unwrap();
+
+    assert_eq!(*requested.lock().unwrap(), vec![1, 2, 3]);
+    assert_eq!(listing.total, Some(45));
+    assert_eq!(listing.entries.len(), 45);
+    assert!(listing.validate().is_ok());
+    assert_eq!(listing.entries.last().unwrap().card_no.to_string(), "SYN-045");
+}
+
+// ちょうど1ページに収まる件数なら、次のページは取得しない
+#[tokio::test]
+async fn links_from_a_single_page() {
+    let (endpoint, requested) = serve_listing(21);
+    let links = SearchQuery::any_product().endpoint(&endpoint).fetch_links().await.unwrap();
+
+    assert_eq!(*requested.lock().unwrap(), vec![1]);
+    assert_eq!(links.len(), 21);
+    assert_eq!(links[0], "/products/wixoss/card_list.php?card=card_detail&card_no=SYN-001");
+}
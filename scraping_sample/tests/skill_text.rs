@@ -0,0 +1,35 @@
+use scraping_sample::wixoss::skill_text::{tokenize, IconKind, RenderMode, SkillRenderer, SkillToken};
+
+const SOURCE: &str = r#"<img src="https://www.takaratomy.co.jp/products/wixoss/img/card/icon/icon_txt_auto.png" height="23" alt="【自】" /><img src="https://www.takaratomy.co.jp/products/wixoss/img/card/icon/icon_txt_turn_01.png" height="23" alt="《ターン１回》" />：<img src="https://www.takaratomy.co.jp/products/wixoss/img/card/icon/icon_txt_red2.png" height="23" alt="《赤2》" />を支払う。<br>カードを１枚引く。"#;
+
+#[test]
+fn icons_are_typed_tokens() {
+    let kinds: Vec<IconKind> = tokenize(SOURCE).into_iter()
+        .filter_map(|t| match t {
+            SkillToken::Icon(icon) => Some(icon.kind),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(kinds, vec![IconKind::Timing, IconKind::OncePerTurn, IconKind::Color]);
+}
+
+#[test]
+fn render_modes() {
+    assert_eq!(
+        SkillRenderer::new(RenderMode::Plain).render(SOURCE),
+        "【自】《ターン１回》：《赤》を支払う。\nカードを１枚引く。"
+    );
+
+    let markdown = SkillRenderer::new(RenderMode::Markdown).icon_dir("assets/icons/").render(SOURCE);
+    assert!(markdown.starts_with("![【自】](assets/icons/icon_txt_auto.png)"));
+    assert!(markdown.contains("![《赤》](assets/icons/icon_txt_red2.png)を支払う。  \n"));
+
+    let html = SkillRenderer::new(RenderMode::Html).render(SOURCE);
+    assert!(html.contains(r#"<img class="icon icon-color" src="icons/icon_txt_red2.png" alt="《赤》">"#));
+    assert!(html.contains("<br>カード"));
+
+    let tokens: serde_json::Value = serde_json::from_str(&SkillRenderer::new(RenderMode::Tokens).render(SOURCE)).unwrap();
+    assert_eq!(tokens[0]["type"], "Icon");
+    assert_eq!(tokens[0]["value"]["kind"], "Timing");
+}
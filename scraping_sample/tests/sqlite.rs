@@ -0,0 +1,108 @@
+mod common;
+
+use scraping_sample::export::CardPool;
+use scraping_sample::products::{Product, ProductCatalog, ProductKind};
+use scraping_sample::sqlite::export_sqlite;
+use scraping_sample::wixoss::Card;
+use sqlx::{Connection, Row, SqliteConnection};
+use std::path::Path;
+
+#[tokio::test]
+async fn export_fixture_pool_to_sqlite() {
+    let pool = CardPool::from_cache_dir(Path::new("tests/fixtures"));
+    let path = common::temp_dir("sqlite_export").join("cards.sqlite");
+
+    // 二回書き出しても作り直されるだけ
+    export_sqlite(&pool.cards, &ProductCatalog::default(), &path).await.unwrap();
+    export_sqlite(&pool.cards, &ProductCatalog::default(), &path).await.unwrap();
+
+    let mut conn = SqliteConnection::connect(&format!("sqlite://{}", path.display())).await.unwrap();
+
+    let count: i64 = sqlx::query("select count(*) from cards").fetch_one(&mut conn).await.unwrap().get(0);
+    assert_eq!(count as usize, pool.cards.len());
+
+    let indexes: Vec<String> = sqlx::query("select name from sqlite_master where type = 'index' and tbl_name = 'cards'")
+        .fetch_all(&mut conn).await.unwrap()
+        .iter().map(|r| r.get(0)).collect();
+    for column in ["name", "pronounce", "card_type", "color", "level"] {
+        assert!(indexes.contains(&format!("idx_cards_{}", column)), "{:?}", indexes);
+    }
+
+    // WXDi-P12-071 はドローを持つ。card_featuresとビットマスクのどちらで引いても同じ
+    let by_table: Vec<String> = sqlx::query(
+        "select cf.card_no from card_features cf join features f on f.id = cf.feature_id where f.name = 'Draw' order by cf.card_no")
+        .fetch_all(&mut conn).await.unwrap()
+        .iter().map(|r| r.get(0)).collect();
+    let by_bits: Vec<String> = sqlx::query(
+        "select c.no from cards c join features f on f.name = 'Draw'
+         where (case f.word when 0 then c.feature_bits_low else c.feature_bits_high end) & f.bit != 0 order by c.no")
+        .fetch_all(&mut conn).await.unwrap()
+        .iter().map(|r| r.get(0)).collect();
+    assert!(by_table.contains(&"WXDi-P12-071".to_string()), "{:?}", by_table);
+    assert_eq!(by_table, by_bits);
+
+    let skills: i64 = sqlx::query("select count(*) from skills where card_no = 'WXDi-P12-071'")
+        .fetch_one(&mut conn).await.unwrap().get(0);
+    assert!(skills > 0);
+
+    let product: String = sqlx::query("select product_code from product_cards where card_no = 'WXDi-P12-071'")
+        .fetch_one(&mut conn).await.unwrap().get(0);
+    assert_eq!(product, "WXDi-P12");
+
+    conn.close().await.unwrap();
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn export_catalog_products_to_sqlite() {
+    let pool = CardPool::from_cache_dir(Path::new("tests/fixtures"));
+    let path = common::temp_dir("sqlite_export_catalog").join("cards.sqlite");
+
+    // WXDi-P12-071 が構築済みデッキに再録されたことにする
+    let mut catalog = ProductCatalog::default();
+    catalog.upsert(Product {
+        code: "WXDi-D09".into(),
+        kind: ProductKind::Starter,
+        name: Some("構築済みデッキ".into()),
+        release_date: Some("2022-06-24".into()),
+        card_count: Some(40),
+    });
+    catalog.link("WXDi-P12", "WXDi-P12-071".parse().unwrap());
+    catalog.link("WXDi-D09", "WXDi-P12-071".parse().unwrap());
+    export_sqlite(&pool.cards, &catalog, &path).await.unwrap();
+
+    let mut conn = SqliteConnection::connect(&format!("sqlite://{}", path.display())).await.unwrap();
+
+    let products: Vec<String> = sqlx::query("select product_code from product_cards where card_no = 'WXDi-P12-071' order by product_code")
+        .fetch_all(&mut conn).await.unwrap()
+        .iter().map(|r| r.get(0)).collect();
+    assert_eq!(products, vec!["WXDi-D09", "WXDi-P12"]);
+
+    let row = sqlx::query("select kind, name, release_date, card_count from products where code = 'WXDi-D09'")
+        .fetch_one(&mut conn).await.unwrap();
+    assert_eq!(row.get::<String, _>(0), "Starter");
+    assert_eq!(row.get::<String, _>(1), "構築済みデッキ");
+    assert_eq!(row.get::<String, _>(2), "2022-06-24");
+    assert_eq!(row.get::<i64, _>(3), 40);
+
+    conn.close().await.unwrap();
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
+
+// 名前が空のカードがあっても、書き出し全体を失敗させない
+#[tokio::test]
+async fn card_without_name_is_exported() {
+    let html = std::fs::read_to_string("tests/fixtures/signi/WX22-025.html").unwrap()
+        .replace("天空の主神　ゼウシアス", "");
+    let card = Card::card_from_html(&html).unwrap();
+    let path = common::temp_dir("sqlite_export_noname").join("cards.sqlite");
+    export_sqlite(&[card], &ProductCatalog::default(), &path).await.unwrap();
+
+    let mut conn = SqliteConnection::connect(&format!("sqlite://{}", path.display())).await.unwrap();
+    let name: String = sqlx::query("select name from cards where no = 'WX22-025'")
+        .fetch_one(&mut conn).await.unwrap().get(0);
+    assert_eq!(name, "");
+
+    conn.close().await.unwrap();
+    std::fs::remove_dir_all(path.parent().unwrap()).unwrap();
+}
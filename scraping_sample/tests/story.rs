@@ -0,0 +1,76 @@
+use scraping_sample::wixoss::{Card, Story, StoryTag};
+
+fn load(fixture: &str) -> String {
+    std::fs::read_to_string(format!("tests/fixtures/{}", fixture)).unwrap()
+}
+
+// WX22-025 のストーリー欄("-")を差し替えてパースする
+fn card_with_story(cell: &str) -> Card {
+    let html = load("signi/WX22-025.html");
+    let label = html.find("<dt>ストーリー</dt>").unwrap();
+    let start = label + html[label..].find("<dd>").unwrap() + "<dd>".len();
+    let end = start + html[start..].find("</dd>").unwrap();
+
+    let html = format!("{}{}{}", &html[..start], cell, &html[end..]);
+    Card::card_from_html(&html).unwrap()
+}
+
+#[test]
+fn unset_story_is_empty() {
+    let card = card_with_story("\n    -\n    ");
+    assert!(card.story.is_empty());
+    assert!(!card.is_dissona());
+}
+
+#[test]
+fn dissona_icon_is_detected() {
+    let card = Card::card_from_html(&load("signi/WXDi-P12-071.html")).unwrap();
+    assert_eq!(card.story.tags(), &[StoryTag::Dissona]);
+    assert!(card.is_dissona());
+}
+
+// 未対応のアイコンはファイル名で残し、アイコン以外の文字列も残す。同じアイコンは一つにまとめる
+#[test]
+fn other_icons_and_text_are_kept() {
+    let card = card_with_story(concat!(
+        r#"<img src="https://www.takaratomy.co.jp/products/wixoss/img/card/icon/icon_txt_dissona.png" alt="" />"#,
+        r#"<img src="https://www.takaratomy.co.jp/products/wixoss/img/card/icon/icon_txt_dissona.png" alt="" />"#,
+        r#"<img src="https://www.takaratomy.co.jp/products/wixoss/img/card/icon/icon_txt_story_new.png" alt="" />"#,
+        " ディーヴァ ",
+    ));
+
+    assert_eq!(card.story.tags(), &[
+        StoryTag::Dissona,
+        StoryTag::Icon("story_new".into()),
+        StoryTag::Text("ディーヴァ".into()),
+    ]);
+    assert!(card.is_dissona());
+}
+
+#[test]
+fn current_format_is_read_back() {
+    let story: Story = serde_json::from_str(r#"["Dissona", {"Icon": "story_new"}, {"Text": "ディーヴァ"}]"#).unwrap();
+    assert_eq!(story.tags(), &[
+        StoryTag::Dissona,
+        StoryTag::Icon("story_new".into()),
+        StoryTag::Text("ディーヴァ".into()),
+    ]);
+
+    let card = Card::card_from_html(&load("signi/WXDi-P12-071.html")).unwrap();
+    let json = serde_json::to_string(&card.story).unwrap();
+    assert_eq!(serde_json::from_str::<Story>(&json).unwrap(), card.story);
+}
+
+// 以前は "ディソナ" または "" の文字列で書き出していた
+#[test]
+fn legacy_format_is_read() {
+    let empty: Story = serde_json::from_str(r#""""#).unwrap();
+    assert!(empty.is_empty());
+
+    let dissona: Story = serde_json::from_str(r#""ディソナ""#).unwrap();
+    assert_eq!(dissona.tags(), &[StoryTag::Dissona]);
+    assert!(dissona.is_dissona());
+
+    let other: Story = serde_json::from_str(r#""ディーヴァ""#).unwrap();
+    assert_eq!(other.tags(), &[StoryTag::Text("ディーヴァ".into())]);
+}
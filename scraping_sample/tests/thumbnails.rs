@@ -0,0 +1,119 @@
+mod common;
+
+use common::temp_dir;
+use image::{ImageBuffer, Rgb};
+use scraping_sample::images::ImageCache;
+use scraping_sample::thumbnails::{ThumbnailError, ThumbnailFormat, ThumbnailSize, Thumbnailer};
+use scraping_sample::wixoss::card_no::CardNo;
+use std::path::{Path, PathBuf};
+
+// カード画像と同じ縦長の画像
+fn write_source(dir: &Path) -> PathBuf {
+    let image = ImageBuffer::from_fn(400, 558, |x, y| Rgb([(x % 256) as u8, (y % 256) as u8, 128]));
+    let path = dir.join("source.jpg");
+    image.save(&path).unwrap();
+    path
+}
+
+#[test]
+fn thumbnails_keyed_by_card_no() {
+    let dir = temp_dir("thumbnails");
+    let source = write_source(&dir);
+    let card_no: CardNo = "WXDi-P12-071".parse().unwrap();
+
+    let thumbnailer = Thumbnailer::new(&dir.join("thumbnails"));
+    let paths = thumbnailer.generate(&card_no, &source).unwrap();
+    assert_eq!(paths, [
+        dir.join("thumbnails/small/WXDi-P12-071.webp"),
+        dir.join("thumbnails/medium/WXDi-P12-071.webp"),
+    ]);
+
+    let small = image::open(&paths[0]).unwrap();
+    assert_eq!((small.width(), small.height()), (160, 223));
+    let medium = image::open(&paths[1]).unwrap();
+    assert_eq!((medium.width(), medium.height()), (320, 446));
+
+    // 作成済みのものは作り直さない
+    let modified = std::fs::metadata(&paths[0]).unwrap().modified().unwrap();
+    thumbnailer.generate(&card_no, &source).unwrap();
+    assert_eq!(std::fs::metadata(&paths[0]).unwrap().modified().unwrap(), modified);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn jpeg_and_custom_sizes() {
+    let dir = temp_dir("thumbnails_jpeg");
+    let source = write_source(&dir);
+    let card_no: CardNo = "PR-K060".parse().unwrap();
+
+    let thumbnailer = Thumbnailer::new(&dir)
+        .format(ThumbnailFormat::Jpeg)
+        .sizes(ThumbnailSize::parse_list("tiny=80,large=1000").unwrap());
+
+    let tiny = thumbnailer.generate_size(&card_no, &source, "tiny").unwrap();
+    assert_eq!(tiny, dir.join("tiny/PR-K060.jpg"));
+    assert_eq!(image::open(&tiny).unwrap().width(), 80);
+
+    // 元より大きくはしない
+    let large = thumbnailer.generate_size(&card_no, &source, "large").unwrap();
+    assert_eq!(image::open(&large).unwrap().width(), 400);
+
+    assert!(matches!(thumbnailer.generate_size(&card_no, &source, "medium"), Err(ThumbnailError::UnknownSize(_))));
+    assert!(ThumbnailSize::parse_list("small=0").is_err());
+    assert!(ThumbnailSize::parse_list("small").is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn on_demand_needs_cached_image() {
+    let dir = temp_dir("thumbnails_on_demand");
+    let thumbnailer = Thumbnailer::new(&dir.join("thumbnails"));
+    let images = ImageCache::new(&dir.join("images"));
+
+    // 詳細ページはあるが画像は未取得
+    std::fs::create_dir_all(dir.join("text/WXDi-P12")).unwrap();
+    std::fs::copy("tests/fixtures/signi/WXDi-P12-071.html", dir.join("text/WXDi-P12/071.html")).unwrap();
+
+    let card_no: CardNo = "WXDi-P12-071".parse().unwrap();
+    let result = thumbnailer.generate_on_demand(&card_no, "small", &dir.join("text"), &images);
+    assert!(matches!(result, Err(ThumbnailError::SourceNotFound(_))));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// サイズ名はパスの一部になるので、設定に無い名前はパスを作る前に弾く
+#[test]
+fn on_demand_rejects_unknown_size() {
+    let dir = temp_dir("thumbnails_unknown_size");
+    let thumbnailer = Thumbnailer::new(&dir.join("thumbnails"));
+    let images = ImageCache::new(&dir.join("images"));
+
+    // 出力先の外に置いたファイル。サイズ名で辿れても返してはいけない
+    std::fs::write(dir.join("WXDi-P12-071.webp"), b"secret").unwrap();
+
+    let card_no: CardNo = "WXDi-P12-071".parse().unwrap();
+    for size in ["..", "../..", "large"] {
+        let result = thumbnailer.generate_on_demand(&card_no, size, &dir.join("text"), &images);
+        assert!(matches!(result, Err(ThumbnailError::UnknownSize(_))), "{}", size);
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// 一時ファイルは残さない
+#[test]
+fn no_temporary_files_left() {
+    let dir = temp_dir("thumbnails_tmp");
+    let source = write_source(&dir);
+    let card_no: CardNo = "WXDi-P12-071".parse().unwrap();
+
+    Thumbnailer::new(&dir.join("thumbnails")).generate(&card_no, &source).unwrap();
+    let names: Vec<String> = std::fs::read_dir(dir.join("thumbnails/small")).unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(names, ["WXDi-P12-071.webp"]);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -1,9 +1,17 @@
-use axum::{response::Html, routing::get, Router};
+use std::path::Path;
+use axum::{extract, http::{header, StatusCode}, response::{Html, IntoResponse, Response}, routing::get, Router};
+use scraping_sample::images::{ImageCache, IMAGE_CACHE_DIR};
+use scraping_sample::thumbnails::{ThumbnailError, Thumbnailer, THUMBNAIL_DIR};
+use scraping_sample::wixoss::card_no::CardNo;
+
+const TEXT_CACHE_DIR: &str = "./text_cache";
 
 #[tokio::main]
 async fn main() {
     // build our application with a single route
-    let app = Router::new().route("/", get(handler));
+    let app = Router::new()
+        .route("/", get(handler))
+        .route("/thumbnails/:size/:file", get(thumbnail));
 
     let port = 3000;
 
@@ -18,4 +26,33 @@ async fn main() {
 
 async fn handler() -> Html<&'static str> {
     Html("<html><head><title>Home</title></head><body><h1>Hello, World!</h1></body></html>")
-}
\ No newline at end of file
+}
+
+// /thumbnails/small/WXDi-P12-071.webp
+// 縮小画像が無ければ、画像キャッシュにある元画像からその場で作る
+async fn thumbnail(extract::Path((size, file)): extract::Path<(String, String)>) -> Response {
+    let thumbnailer = Thumbnailer::new(Path::new(THUMBNAIL_DIR));
+    let format = thumbnailer.get_format();
+
+    let card_no: CardNo = match file.strip_suffix(&format!(".{}", format.extension())).map(|no| no.parse()) {
+        Some(Ok(card_no)) => card_no,
+        _ => return StatusCode::NOT_FOUND.into_response(),
+    };
+
+    // 画像の縮小は重いのでブロッキング用のスレッドで行う
+    let generated = tokio::task::spawn_blocking(move || {
+        let images = ImageCache::new(Path::new(IMAGE_CACHE_DIR));
+        thumbnailer.generate_on_demand(&card_no, &size, Path::new(TEXT_CACHE_DIR), &images)
+            .and_then(|path| std::fs::read(path).map_err(ThumbnailError::Io))
+    }).await;
+
+    match generated {
+        Ok(Ok(bytes)) => ([(header::CONTENT_TYPE, format.content_type())], bytes).into_response(),
+        Ok(Err(ThumbnailError::UnknownSize(_) | ThumbnailError::SourceNotFound(_))) => StatusCode::NOT_FOUND.into_response(),
+        Ok(Err(e)) => {
+            eprintln!("thumbnail failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}